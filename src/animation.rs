@@ -0,0 +1,516 @@
+use crate::manifest::{Manifest, Step};
+
+/// what makes an animation go from one state to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    /// a key was pressed
+    KeyPress,
+    /// no key was pressed since the specified number of milliseconds, it's compared with the
+    /// 32 bit timer so it can be longer than the 16 bit durations of the frames
+    IdleTimeout(u32),
+    /// the WPM is strictly above the value
+    WpmAbove(u8),
+    /// the WPM is strictly below the value
+    WpmBelow(u8),
+    /// all the frames of the current state were displayed at least once
+    End,
+}
+
+impl std::str::FromStr for Trigger {
+    type Err = Box<dyn std::error::Error>;
+
+    /// parse a trigger written like `keypress`, `idle:500`, `wpm>30`, `wpm<10` or `end`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "invalid trigger `{}`, expected `keypress`, `idle:MS`, `wpm>N`, `wpm<N` or `end`",
+                s
+            )
+        };
+        if s == "keypress" {
+            Ok(Trigger::KeyPress)
+        } else if s == "end" {
+            Ok(Trigger::End)
+        } else if let Some(ms) = s.strip_prefix("idle:") {
+            Ok(Trigger::IdleTimeout(ms.parse().map_err(|_| invalid())?))
+        } else if let Some(wpm) = s.strip_prefix("wpm>") {
+            Ok(Trigger::WpmAbove(wpm.parse().map_err(|_| invalid())?))
+        } else if let Some(wpm) = s.strip_prefix("wpm<") {
+            Ok(Trigger::WpmBelow(wpm.parse().map_err(|_| invalid())?))
+        } else {
+            Err(invalid().into())
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transition {
+    pub from: String,
+    pub to: String,
+    pub trigger: Trigger,
+}
+
+/// a state of the animation and the frames displayed in a loop while we are in this state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    pub name: String,
+    pub steps: Vec<Step>,
+}
+
+/// an animation driven by the keyboard, like the bongo cat going from idle to tapping.
+///
+/// The transitions are checked in order, the first one triggered is the only one applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation {
+    pub states: Vec<State>,
+    pub transitions: Vec<Transition>,
+    /// the index of the state the animation starts in
+    pub initial: usize,
+}
+
+impl Animation {
+    /// create an animation, all the transitions must go from and to existing states. The
+    /// generated C counts the states and the steps with a `uint8_t`, so there can't be more
+    /// than 255 of each
+    pub fn new(
+        states: Vec<State>,
+        transitions: Vec<Transition>,
+        initial: &str,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let find = |name: &str| {
+            states
+                .iter()
+                .position(|state| state.name == name)
+                .ok_or_else(|| format!("the state `{}` does not exist", name))
+        };
+        let initial = find(initial)?;
+        for transition in &transitions {
+            find(&transition.from)?;
+            find(&transition.to)?;
+        }
+        if let Some(state) = states.iter().find(|state| state.steps.is_empty()) {
+            return Err(format!("the state `{}` has no frame", state.name).into());
+        }
+        if let Some(state) = states.iter().find(|state| state.steps.len() > 255) {
+            return Err(format!(
+                "the state `{}` has {} steps, the maximum is 255",
+                state.name,
+                state.steps.len()
+            )
+            .into());
+        }
+        if states.len() > 255 {
+            return Err(format!("{} states, the maximum is 255", states.len()).into());
+        }
+        // the names are uppercased in the generated C, where they must stay different
+        for (i, state) in states.iter().enumerate() {
+            if let Some(other) = states[..i]
+                .iter()
+                .find(|other| other.name.to_uppercase() == state.name.to_uppercase())
+            {
+                return Err(format!(
+                    "the states `{}` and `{}` have the same name in C",
+                    other.name, state.name
+                )
+                .into());
+            }
+        }
+
+        Ok(Self {
+            states,
+            transitions,
+            initial,
+        })
+    }
+
+    /// create an animation from the sequences and transitions of a manifest.
+    /// If no initial state is specified the first sequence is used
+    pub fn from_manifest(manifest: &Manifest) -> Result<Self, Box<dyn std::error::Error>> {
+        let states = manifest
+            .sequences
+            .iter()
+            .map(|sequence| State {
+                name: sequence.name.clone(),
+                steps: sequence.steps.clone(),
+            })
+            .collect();
+        let initial = match &manifest.initial {
+            Some(initial) => initial.as_str(),
+            None => manifest
+                .sequences
+                .first()
+                .map(|sequence| sequence.name.as_str())
+                .ok_or("an animation needs at least one sequence")?,
+        };
+
+        Self::new(states, manifest.transitions.clone(), initial)
+    }
+
+    /// return the index of a state
+    pub fn state_index(&self, name: &str) -> Option<usize> {
+        self.states.iter().position(|state| state.name == name)
+    }
+
+    /// start playing the animation
+    pub fn player(&self) -> Player<'_> {
+        Player {
+            animation: self,
+            state: self.initial,
+            step: 0,
+            step_start: 0,
+            last_keypress: 0,
+            keypressed: false,
+            ended: false,
+        }
+    }
+
+    /// generate the C state machine playing the animation on a QMK keyboard.
+    ///
+    /// It expects the `<STATE>_SEQUENCE`, `<STATE>_SIZES`, `<STATE>_DURATIONS` and
+    /// `<STATE>_LENGTH` generated for every sequence of the manifest.
+    /// Call `animation_key_pressed()` from `process_record_user` and `animation_update(wpm)`
+    /// from `oled_task_user`, when it returns `true` a new frame must be decoded.
+    pub fn generate_c(&self) -> String {
+        let mut c = String::new();
+        let enum_name = |state: &State| format!("ANIMATION_{}", state.name.to_uppercase());
+        let table = |suffix: &str| {
+            self.states
+                .iter()
+                .map(|state| format!("{}_{}", state.name.to_uppercase(), suffix))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let len = self.states.len();
+
+        c.push_str(&format!(
+            "enum animation_state {{ {} }};\n",
+            self.states
+                .iter()
+                .map(enum_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        c.push_str(&format!(
+            "static const uint8_t *const *const ANIMATION_SEQUENCES[{}] = {{ {} }};\n",
+            len,
+            table("SEQUENCE")
+        ));
+        c.push_str(&format!(
+            "static const uint16_t *const ANIMATION_SIZES[{}] = {{ {} }};\n",
+            len,
+            table("SIZES")
+        ));
+        c.push_str(&format!(
+            "static const uint16_t *const ANIMATION_DURATIONS[{}] = {{ {} }};\n",
+            len,
+            table("DURATIONS")
+        ));
+        c.push_str(&format!(
+            "static const uint8_t ANIMATION_LENGTHS[{}] = {{ {} }};\n",
+            len,
+            table("LENGTH")
+        ));
+        c.push_str(&format!(
+            "
+static uint8_t animation_state = {};
+static uint8_t animation_step = 0;
+static uint32_t animation_timer = 0;
+static uint32_t animation_last_keypress = 0;
+static bool animation_keypressed = false;
+static bool animation_ended = false;
+
+void animation_key_pressed(void) {{
+    animation_last_keypress = timer_read32();
+    animation_keypressed = true;
+}}
+
+static void animation_set_state(uint8_t state) {{
+    animation_state = state;
+    animation_step = 0;
+    animation_timer = timer_read32();
+    animation_ended = false;
+}}
+
+/* return the frame to display */
+const uint8_t *animation_frame(void) {{
+    return ANIMATION_SEQUENCES[animation_state][animation_step];
+}}
+
+/* return the size of the frame to display */
+uint16_t animation_frame_size(void) {{
+    return ANIMATION_SIZES[animation_state][animation_step];
+}}
+
+/* return true when the frame to display changed */
+bool animation_update(uint8_t wpm) {{
+    bool keypressed = animation_keypressed;
+    animation_keypressed = false;
+    (void)keypressed;
+
+    switch (animation_state) {{
+",
+            enum_name(&self.states[self.initial])
+        ));
+
+        for state in &self.states {
+            c.push_str(&format!("    case {}:\n", enum_name(state)));
+            for transition in self
+                .transitions
+                .iter()
+                .filter(|transition| transition.from == state.name)
+            {
+                let condition = match transition.trigger {
+                    Trigger::KeyPress => "keypressed".to_string(),
+                    Trigger::IdleTimeout(ms) => {
+                        format!("timer_elapsed32(animation_last_keypress) >= {}UL", ms)
+                    }
+                    Trigger::WpmAbove(wpm) => format!("wpm > {}", wpm),
+                    Trigger::WpmBelow(wpm) => format!("wpm < {}", wpm),
+                    Trigger::End => "animation_ended".to_string(),
+                };
+                let to = &self.states[self.state_index(&transition.to).unwrap()];
+                c.push_str(&format!(
+                    "        if ({}) {{\n            animation_set_state({});\n            return true;\n        }}\n",
+                    condition,
+                    enum_name(to)
+                ));
+            }
+            c.push_str("        break;\n");
+        }
+
+        c.push_str(
+            "    }
+
+    if (timer_elapsed32(animation_timer) >= ANIMATION_DURATIONS[animation_state][animation_step]) {
+        animation_timer = timer_read32();
+        animation_step += 1;
+        if (animation_step >= ANIMATION_LENGTHS[animation_state]) {
+            animation_step = 0;
+            animation_ended = true;
+        }
+        return true;
+    }
+    return false;
+}
+",
+        );
+
+        c
+    }
+}
+
+/// play an animation, the time is expressed in milliseconds and only needs to be monotonic
+#[derive(Debug, Clone)]
+pub struct Player<'a> {
+    animation: &'a Animation,
+    state: usize,
+    step: usize,
+    step_start: u64,
+    last_keypress: u64,
+    keypressed: bool,
+    ended: bool,
+}
+
+impl<'a> Player<'a> {
+    /// notify the animation that a key was pressed
+    pub fn key_press(&mut self, now: u64) {
+        self.last_keypress = now;
+        self.keypressed = true;
+    }
+
+    /// apply the transitions and advance the animation, this is exactly what the generated
+    /// `animation_update` does.
+    /// Return `true` when the frame to display changed
+    pub fn update(&mut self, now: u64, wpm: u8) -> bool {
+        let keypressed = std::mem::take(&mut self.keypressed);
+        let current = &self.animation.states[self.state].name;
+
+        let triggered = self
+            .animation
+            .transitions
+            .iter()
+            .filter(|transition| &transition.from == current)
+            .find(|transition| match transition.trigger {
+                Trigger::KeyPress => keypressed,
+                Trigger::IdleTimeout(ms) => now.saturating_sub(self.last_keypress) >= ms as u64,
+                Trigger::WpmAbove(threshold) => wpm > threshold,
+                Trigger::WpmBelow(threshold) => wpm < threshold,
+                Trigger::End => self.ended,
+            });
+
+        if let Some(transition) = triggered {
+            self.state = self.animation.state_index(&transition.to).unwrap();
+            self.step = 0;
+            self.step_start = now;
+            self.ended = false;
+            return true;
+        }
+
+        let steps = &self.animation.states[self.state].steps;
+        if now.saturating_sub(self.step_start) >= steps[self.step].duration as u64 {
+            self.step_start = now;
+            self.step += 1;
+            if self.step >= steps.len() {
+                self.step = 0;
+                self.ended = true;
+            }
+            return true;
+        }
+        false
+    }
+
+    /// the name of the current state
+    pub fn state(&self) -> &'a str {
+        &self.animation.states[self.state].name
+    }
+
+    /// the name of the frame to display
+    pub fn frame(&self) -> &'a str {
+        &self.animation.states[self.state].steps[self.step].frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(frames: &[&str]) -> Vec<Step> {
+        frames
+            .iter()
+            .map(|frame| Step {
+                frame: frame.to_string(),
+                duration: 100,
+            })
+            .collect()
+    }
+
+    fn animation() -> Animation {
+        let transition = |from: &str, to: &str, trigger| Transition {
+            from: from.to_string(),
+            to: to.to_string(),
+            trigger,
+        };
+        Animation::new(
+            vec![
+                State {
+                    name: "idle".to_string(),
+                    steps: steps(&["idle1", "idle2"]),
+                },
+                State {
+                    name: "prep".to_string(),
+                    steps: steps(&["prep"]),
+                },
+                State {
+                    name: "tap".to_string(),
+                    steps: steps(&["tap1", "tap2"]),
+                },
+            ],
+            vec![
+                transition("idle", "prep", Trigger::KeyPress),
+                transition("prep", "tap", Trigger::WpmAbove(30)),
+                transition("prep", "idle", Trigger::End),
+                transition("tap", "idle", Trigger::IdleTimeout(500)),
+            ],
+            "idle",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_trigger() {
+        assert_eq!("keypress".parse::<Trigger>().unwrap(), Trigger::KeyPress);
+        assert_eq!(
+            "idle:500".parse::<Trigger>().unwrap(),
+            Trigger::IdleTimeout(500)
+        );
+        assert_eq!("wpm>30".parse::<Trigger>().unwrap(), Trigger::WpmAbove(30));
+        assert_eq!("wpm<5".parse::<Trigger>().unwrap(), Trigger::WpmBelow(5));
+        assert_eq!("end".parse::<Trigger>().unwrap(), Trigger::End);
+        assert!("wpm=3".parse::<Trigger>().is_err());
+        assert!("wpm>300".parse::<Trigger>().is_err());
+    }
+
+    #[test]
+    fn test_invalid_animation() {
+        let animation = animation();
+        assert!(Animation::new(animation.states.clone(), vec![], "nope").is_err());
+        let mut transitions = animation.transitions.clone();
+        transitions[0].to = "nope".to_string();
+        assert!(Animation::new(animation.states.clone(), transitions, "idle").is_err());
+
+        // the C enumerators are uppercased
+        let mut states = animation.states.clone();
+        states[1].name = "IDLE".to_string();
+        assert!(Animation::new(states, vec![], "idle").is_err());
+
+        // the step of the generated C is a uint8_t
+        let mut states = animation.states;
+        let step = states[0].steps[0].clone();
+        states[0].steps = vec![step; 300];
+        assert!(Animation::new(states, vec![], "idle").is_err());
+    }
+
+    #[test]
+    fn test_player() {
+        let animation = animation();
+        let mut player = animation.player();
+        assert_eq!((player.state(), player.frame()), ("idle", "idle1"));
+
+        assert!(!player.update(50, 0));
+        assert!(player.update(100, 0));
+        assert_eq!(player.frame(), "idle2");
+        assert!(player.update(200, 0));
+        assert_eq!(player.frame(), "idle1");
+
+        player.key_press(210);
+        assert!(player.update(210, 0));
+        assert_eq!((player.state(), player.frame()), ("prep", "prep"));
+        // the key press was consumed by the transition
+        assert!(!player.update(220, 0));
+
+        assert!(player.update(230, 40));
+        assert_eq!((player.state(), player.frame()), ("tap", "tap1"));
+        assert!(player.update(330, 40));
+        assert_eq!(player.frame(), "tap2");
+
+        // no key were pressed since 500ms
+        assert!(player.update(710, 40));
+        assert_eq!(player.state(), "idle");
+    }
+
+    #[test]
+    fn test_end_trigger() {
+        let animation = animation();
+        let mut player = animation.player();
+        player.key_press(0);
+        player.update(0, 0);
+        assert_eq!(player.state(), "prep");
+        // the prep state has only one frame, it ends after its duration
+        assert!(player.update(100, 0));
+        assert_eq!(player.state(), "prep");
+        assert!(player.update(110, 0));
+        assert_eq!(player.state(), "idle");
+    }
+
+    #[test]
+    fn test_generate_c() {
+        let c = animation().generate_c();
+        assert!(
+            c.contains("enum animation_state { ANIMATION_IDLE, ANIMATION_PREP, ANIMATION_TAP };")
+        );
+        assert!(c.contains("static uint8_t animation_state = ANIMATION_IDLE;"));
+        assert!(c.contains(
+            "    case ANIMATION_PREP:
+        if (wpm > 30) {
+            animation_set_state(ANIMATION_TAP);
+            return true;
+        }
+        if (animation_ended) {
+            animation_set_state(ANIMATION_IDLE);
+            return true;
+        }
+        break;"
+        ));
+        assert!(c.contains("timer_elapsed32(animation_last_keypress) >= 500UL"));
+    }
+}
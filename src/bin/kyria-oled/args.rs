@@ -0,0 +1,379 @@
+use kyria_oled::budget::{parse_bytes, Mcu};
+use kyria_oled::emit::{BinEmitter, CEmitter, Emitter, PythonEmitter, RustEmitter, Style};
+use kyria_oled::generator::{merge_frames, BaseStrategy, Codec, Merge};
+use kyria_oled::graphics::{detect, Palette, Protocol};
+use kyria_oled::lossy::Budget;
+use kyria_oled::manifest::Manifest;
+use kyria_oled::scale::parse_dimensions;
+use kyria_oled::zmk::ZmkFormat;
+use kyria_oled::{Frame, RenderStyle};
+use std::path::Path;
+
+/// the language of the generated arrays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    C,
+    Rust,
+    Python,
+    /// a binary blob with an index, see `BinEmitter`
+    Bin,
+}
+
+impl std::str::FromStr for Lang {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "c" => Ok(Lang::C),
+            "rust" => Ok(Lang::Rust),
+            "python" => Ok(Lang::Python),
+            "bin" => Ok(Lang::Bin),
+            s => Err(format!(
+                "unknown language `{}`, expected `c`, `rust`, `python` or `bin`",
+                s
+            )
+            .into()),
+        }
+    }
+}
+
+/// what `gen` generates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// the arrays and the state machine, printed on stdout
+    C,
+    /// a `.h` and `.c` QMK module
+    Qmk,
+    /// a `.h` and `.c` ZMK module
+    Zmk(ZmkFormat),
+}
+
+impl std::str::FromStr for Target {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "c" => Ok(Target::C),
+            "qmk" => Ok(Target::Qmk),
+            "zmk" => Ok(Target::Zmk(ZmkFormat::Lvgl)),
+            // `zmk-lvgl` or `zmk-raw`
+            s if s.starts_with("zmk-") => Ok(Target::Zmk(s["zmk-".len()..].parse()?)),
+            s => Err(format!(
+                "unknown target `{}`, expected `c`, `qmk`, `zmk` or `zmk-raw`",
+                s
+            )
+            .into()),
+        }
+    }
+}
+
+/// the flags shared by all the subcommands, a subcommand ignores the flags it doesn't need
+#[derive(Debug, Clone)]
+pub struct Args {
+    /// all the arguments that are not flags
+    pub files: Vec<String>,
+    /// `--size WxH`
+    pub size: Option<(usize, usize)>,
+    /// `--codec rle|raw`
+    pub codec: Codec,
+    /// `--base STRATEGY`, what's accepted depends on the subcommand
+    pub base: Option<String>,
+    /// `--lang c|rust|python|bin`
+    pub lang: Option<Lang>,
+    /// `--prefix PREFIX`, `--hex` and `--width N`
+    pub style: Style,
+    /// `--qualifier QUALIFIER`
+    pub qualifier: Option<String>,
+    /// `--section NAME`
+    pub section: Option<String>,
+    /// `--target c|qmk|zmk|zmk-raw`
+    pub target: Target,
+    /// `--manifest FILE`
+    pub manifest: Option<String>,
+    /// `--out DIR`
+    pub out: Option<String>,
+    /// `--source FILE`
+    pub source: Option<String>,
+    /// `--tolerance PIXELS`, overrides the one of the manifest
+    pub tolerance: Option<usize>,
+    /// `--budget PIXELS` or `--max-size BYTES`
+    pub budget: Option<Budget>,
+    /// every `--mcu MCU`, all the profiles when there's none
+    pub mcus: Vec<Mcu>,
+    /// `--used FLASH:RAM`, the memory used by the rest of the firmware
+    pub used: (usize, usize),
+    /// `--render blocks|half|braille|inverse`, how `view`, `play` and `simulate` draw the frames
+    pub render: RenderStyle,
+    /// `--graphics auto|sixel|kitty`, `None` to draw the frames as text
+    pub graphics: Option<Protocol>,
+    /// `--scale N`, the size of a pixel of the frames in pixels of the images
+    pub scale: usize,
+    /// `--palette white|blue`
+    pub palette: Palette,
+    /// `--duration MS`
+    pub duration: u32,
+    /// `--repeat N`
+    pub repeat: usize,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut parsed = Args {
+            files: Vec::new(),
+            size: None,
+            codec: Codec::Rle,
+            base: None,
+            lang: None,
+            style: Style::default(),
+            qualifier: None,
+            section: None,
+            target: Target::C,
+            manifest: None,
+            out: None,
+            source: None,
+            tolerance: None,
+            budget: None,
+            mcus: Vec::new(),
+            used: (0, 0),
+            render: RenderStyle::Blocks,
+            graphics: None,
+            scale: 2,
+            palette: Palette::White,
+            duration: 200,
+            repeat: 2,
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value after `{}`", arg))
+            };
+            match arg.as_str() {
+                "--size" => {
+                    let size = value()?;
+                    parsed.size = Some(parse_dimensions(size).ok_or_else(|| {
+                        format!("invalid size `{}`, expected `WIDTHxHEIGHT`", size)
+                    })?)
+                }
+                "--codec" => parsed.codec = value()?.parse()?,
+                "--base" => parsed.base = Some(value()?.clone()),
+                "--lang" => parsed.lang = Some(value()?.parse()?),
+                "--prefix" => parsed.style.prefix = value()?.clone(),
+                "--hex" => parsed.style.hex = true,
+                "--width" => {
+                    let width = value()?;
+                    parsed.style.width = width
+                        .parse()
+                        .map_err(|_| format!("invalid line width `{}`", width))?
+                }
+                "--qualifier" => parsed.qualifier = Some(value()?.clone()),
+                "--section" => parsed.section = Some(value()?.clone()),
+                "--target" => parsed.target = value()?.parse()?,
+                "--manifest" => parsed.manifest = Some(value()?.clone()),
+                "--out" => parsed.out = Some(value()?.clone()),
+                "--source" => parsed.source = Some(value()?.clone()),
+                "--tolerance" => {
+                    let tolerance = value()?;
+                    parsed.tolerance = Some(
+                        tolerance
+                            .parse()
+                            .map_err(|_| format!("invalid tolerance `{}`", tolerance))?,
+                    )
+                }
+                "--budget" => {
+                    let pixels = value()?;
+                    parsed.budget = Some(Budget::Pixels(
+                        pixels
+                            .parse()
+                            .map_err(|_| format!("invalid pixel budget `{}`", pixels))?,
+                    ))
+                }
+                "--max-size" => {
+                    let size = value()?;
+                    parsed.budget = Some(Budget::TotalSize(
+                        size.parse()
+                            .map_err(|_| format!("invalid size `{}`", size))?,
+                    ))
+                }
+                "--mcu" => parsed.mcus.push(value()?.parse()?),
+                "--used" => {
+                    let used = value()?;
+                    let (flash, ram) = used.split_once(':').ok_or_else(|| {
+                        format!("invalid memory usage `{}`, expected `FLASH:RAM`", used)
+                    })?;
+                    parsed.used = (parse_bytes(flash)?, parse_bytes(ram)?)
+                }
+                "--render" => parsed.render = value()?.parse()?,
+                "--graphics" => match value()?.as_str() {
+                    // the half blocks are the most compact text when there's no protocol
+                    "auto" => match detect() {
+                        Some(protocol) => parsed.graphics = Some(protocol),
+                        None => parsed.render = RenderStyle::HalfBlocks,
+                    },
+                    protocol => parsed.graphics = Some(protocol.parse()?),
+                },
+                "--scale" => {
+                    let scale = value()?;
+                    parsed.scale = match scale.parse() {
+                        Ok(scale) if scale > 0 => scale,
+                        _ => return Err(format!("invalid scale `{}`", scale).into()),
+                    }
+                }
+                "--palette" => parsed.palette = value()?.parse()?,
+                "--duration" => {
+                    let duration = value()?;
+                    parsed.duration = duration
+                        .parse()
+                        .map_err(|_| format!("invalid duration `{}`", duration))?
+                }
+                "--repeat" => {
+                    let repeat = value()?;
+                    parsed.repeat = repeat
+                        .parse()
+                        .map_err(|_| format!("invalid repeat count `{}`", repeat))?
+                }
+                flag if flag.starts_with("--") => {
+                    return Err(format!("unknown flag `{}`", flag).into())
+                }
+                file => parsed.files.push(file.to_string()),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// the frames of `frames(true)` replaced by a previous frame within the tolerance
+    pub fn merges(&self) -> Result<Vec<Merge>, Box<dyn std::error::Error>> {
+        match self.manifest()? {
+            Some(manifest) => manifest.merges(),
+            None => Ok(merge_frames(
+                &mut self.frames(false)?,
+                self.tolerance.unwrap_or(0),
+            )),
+        }
+    }
+
+    /// the emitter of the C arrays, with `--qualifier`, `--section` and the style flags
+    pub fn c_emitter(&self) -> CEmitter {
+        let mut emitter = CEmitter {
+            section: self.section.clone(),
+            style: self.style.clone(),
+            ..CEmitter::default()
+        };
+        if let Some(qualifier) = &self.qualifier {
+            emitter.qualifier = qualifier.clone();
+        }
+        emitter
+    }
+
+    /// the emitter of the arrays in `--lang`, or else `default`
+    pub fn emitter(&self, default: Lang) -> Box<dyn Emitter> {
+        let style = self.style.clone();
+        match self.lang.unwrap_or(default) {
+            Lang::C => Box::new(self.c_emitter()),
+            Lang::Rust => Box::new(RustEmitter {
+                style,
+                ..RustEmitter::default()
+            }),
+            Lang::Python => Box::new(PythonEmitter { style }),
+            Lang::Bin => Box::new(BinEmitter::default()),
+        }
+    }
+
+    /// load the manifest given with `--manifest`, if any
+    pub fn manifest(&self) -> Result<Option<Manifest>, Box<dyn std::error::Error>> {
+        let mut manifest = self.manifest.as_deref().map(Manifest::load).transpose()?;
+        if let (Some(manifest), Some(tolerance)) = (&mut manifest, self.tolerance) {
+            manifest.tolerance = tolerance;
+        }
+        Ok(manifest)
+    }
+
+    /// load the frames of the manifest, or else the frame files, named after their file name.
+    /// When `buffer` is set the frames of the manifest are transformed as they are stored in
+    /// the frame buffer, and the frames within the tolerance of a previous frame are merged
+    pub fn frames(&self, buffer: bool) -> Result<Vec<(String, Frame)>, Box<dyn std::error::Error>> {
+        self.frames_with(buffer, buffer)
+    }
+
+    /// same as `frames` but the frames are only merged when `merge` is set
+    pub fn frames_with(
+        &self,
+        buffer: bool,
+        merge: bool,
+    ) -> Result<Vec<(String, Frame)>, Box<dyn std::error::Error>> {
+        let mut frames = match self.manifest()? {
+            Some(manifest) if buffer && merge => manifest.load_buffer_frames()?,
+            Some(manifest) if buffer => manifest.load_unmerged_buffer_frames()?,
+            Some(manifest) if merge => manifest.load_panel_frames()?,
+            Some(manifest) => manifest.load_frames()?,
+            None => self
+                .files
+                .iter()
+                .map(|file| {
+                    let name = Path::new(file)
+                        .file_stem()
+                        .and_then(|name| name.to_str())
+                        .unwrap_or(file)
+                        .to_string();
+                    Ok((name, Frame::create_from_file(file)?))
+                })
+                .collect::<Result<_, Box<dyn std::error::Error>>>()?,
+        };
+
+        if frames.is_empty() {
+            return Err("there is no frame, give some frame files or a `--manifest`".into());
+        }
+        // the frames of the buffer are output in pages of 8 rows
+        if buffer {
+            for (name, frame) in &frames {
+                let (width, height) = frame.dimensions();
+                Frame::check_dimensions(width, height)
+                    .map_err(|e| format!("frame `{}`: {}", name, e))?;
+            }
+        }
+        if merge && self.manifest.is_none() {
+            merge_frames(&mut frames, self.tolerance.unwrap_or(0));
+        }
+        if let Some((width, height)) = self.size {
+            if let Some((name, frame)) = frames
+                .iter()
+                .find(|(_, frame)| frame.dimensions() != (width, height))
+            {
+                return Err(format!(
+                    "frame `{}` is {} × {} instead of {} × {}",
+                    name,
+                    frame.width(),
+                    frame.height(),
+                    width,
+                    height
+                )
+                .into());
+            }
+        }
+        Ok(frames)
+    }
+
+    /// the names of the generated arrays in this order: (base, prefix of the frames), they come
+    /// from the manifest if there is one
+    pub fn array_names(&self) -> Result<(String, String), Box<dyn std::error::Error>> {
+        Ok(match self.manifest()? {
+            Some(manifest) => (manifest.output.base, manifest.output.prefix),
+            None => ("BASE_FRAME".to_string(), String::new()),
+        })
+    }
+
+    /// the base strategy given with `--base`, or else the one of the manifest, or else
+    /// `default`
+    pub fn base_strategy(
+        &self,
+        default: BaseStrategy,
+    ) -> Result<BaseStrategy, Box<dyn std::error::Error>> {
+        match (&self.base, self.manifest()?) {
+            (Some(base), _) => base.parse(),
+            (None, Some(manifest)) => Ok(manifest.base),
+            (None, None) => Ok(default),
+        }
+    }
+}
@@ -0,0 +1,839 @@
+use crate::args::{Args, Lang, Target};
+use kyria_oled::animation::Animation;
+use kyria_oled::budget::{estimate, profiles, Item, Usage};
+use kyria_oled::cycles::{micros, AVR, AVR_CLOCK};
+use kyria_oled::editor::Editor;
+use kyria_oled::generator::{encode_with_codec, BaseStrategy, Codec, Encoded};
+use kyria_oled::graphics;
+use kyria_oled::manifest::Manifest;
+use kyria_oled::parse::{parse_arrays, parse_frames};
+use kyria_oled::verify::{count_different_pixels, pixel_diff};
+use kyria_oled::wpm::Wpm;
+use kyria_oled::{Frame, OutputMode};
+use std::collections::HashMap;
+use std::io::{stdin, stdout, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use termion::event::{Event, Key};
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+
+type Result = std::result::Result<(), Box<dyn std::error::Error>>;
+
+/// draw a frame with the `--graphics` protocol, or else as text with `--render`, and return the
+/// number of lines of the text. The height of an image in lines is unknown, so the cursor
+/// position is saved and restored around the images instead
+fn draw(args: &Args, frame: &Frame) -> (String, Option<u16>) {
+    match args.graphics {
+        Some(protocol) => (
+            graphics::encode(frame, protocol, args.scale, args.palette),
+            None,
+        ),
+        None => {
+            let rendered = frame.render(args.render);
+            let lines = rendered.lines().count() as u16;
+            (rendered, Some(lines))
+        }
+    }
+}
+
+pub fn view(args: &Args) -> Result {
+    let frames = args.frames(false)?;
+    let stdout = stdout();
+    let mut stdout = stdout.into_raw_mode()?;
+    let mut events = stdin().events();
+    let mut current_idx = 0;
+    if args.graphics.is_some() {
+        write!(stdout, "{}", termion::cursor::Save)?;
+    }
+
+    loop {
+        let (name, frame) = &frames[current_idx];
+        let (drawing, lines) = draw(args, frame);
+        if lines.is_none() {
+            write!(stdout, "{}", termion::cursor::Restore)?;
+        }
+        write!(
+            stdout,
+            "Displaying frame {}/{} ({})\r\n{}",
+            current_idx + 1,
+            frames.len(),
+            name,
+            drawing.replace("\n", "\n\r"),
+        )?;
+        if let Some(lines) = lines {
+            write!(stdout, "{}", termion::cursor::Up(lines + 1))?;
+        }
+        stdout.flush()?;
+
+        loop {
+            match events.next().ok_or("stdin closed")?? {
+                Event::Key(Key::Left) => {
+                    current_idx = (current_idx + frames.len() - 1) % frames.len();
+                    break;
+                }
+                Event::Key(Key::Right) => {
+                    current_idx = (current_idx + 1) % frames.len();
+                    break;
+                }
+                Event::Key(Key::Char('q'))
+                | Event::Key(Key::Ctrl('c'))
+                | Event::Key(Key::Ctrl('d')) => {
+                    match lines {
+                        Some(lines) => write!(stdout, "{}", termion::cursor::Down(lines + 1))?,
+                        None => write!(stdout, "\r\n")?,
+                    }
+                    return Ok(());
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// the keys of `edit`, shown under the frame
+const EDIT_KEYS: &str = "arrows/hjkl move (HJKL by 8)  space toggle  v select  y copy  x cut  \
+p paste  u undo  ^r redo  [ ] frame  o onion  s save  q quit";
+
+pub fn edit(args: &Args) -> Result {
+    if args.files.is_empty() {
+        return Err("edit takes the frame files to edit".into());
+    }
+    let frames = args
+        .files
+        .iter()
+        .map(|file| Ok((file.clone(), Frame::create_from_file(file)?)))
+        .collect::<std::result::Result<_, Box<dyn std::error::Error>>>()?;
+    let mut editor = Editor::new(frames);
+    let stdout = stdout();
+    let mut stdout = stdout.into_raw_mode()?;
+    let mut events = stdin().events();
+    let mut message = String::new();
+
+    loop {
+        write!(
+            stdout,
+            "{}{}{}\r\n{}\r\n{}\r\n{}",
+            termion::clear::All,
+            termion::cursor::Goto(1, 1),
+            editor.render().replace("\n", "\r\n"),
+            editor.status(),
+            EDIT_KEYS,
+            message
+        )?;
+        stdout.flush()?;
+        message.clear();
+
+        match events.next().ok_or("stdin closed")?? {
+            Event::Key(Key::Left) | Event::Key(Key::Char('h')) => editor.move_cursor(-1, 0),
+            Event::Key(Key::Right) | Event::Key(Key::Char('l')) => editor.move_cursor(1, 0),
+            Event::Key(Key::Up) | Event::Key(Key::Char('k')) => editor.move_cursor(0, -1),
+            Event::Key(Key::Down) | Event::Key(Key::Char('j')) => editor.move_cursor(0, 1),
+            Event::Key(Key::Char('H')) => editor.move_cursor(-8, 0),
+            Event::Key(Key::Char('L')) => editor.move_cursor(8, 0),
+            Event::Key(Key::Char('K')) => editor.move_cursor(0, -8),
+            Event::Key(Key::Char('J')) => editor.move_cursor(0, 8),
+            Event::Key(Key::Char(' ')) => editor.toggle(),
+            Event::Key(Key::Char('v')) => editor.toggle_selection(),
+            Event::Key(Key::Esc) => editor.anchor = None,
+            Event::Key(Key::Char('y')) => editor.copy(),
+            Event::Key(Key::Char('x')) => editor.cut(),
+            Event::Key(Key::Char('p')) => editor.paste(),
+            Event::Key(Key::Char('u')) => {
+                let done = editor.undo();
+                message = if done {
+                    String::new()
+                } else {
+                    "nothing to undo".to_string()
+                };
+            }
+            Event::Key(Key::Ctrl('r')) => {
+                let done = editor.redo();
+                message = if done {
+                    String::new()
+                } else {
+                    "nothing to redo".to_string()
+                };
+            }
+            Event::Key(Key::Char('[')) | Event::Key(Key::PageUp) => {
+                editor.go_to(editor.current + editor.frames.len() - 1)
+            }
+            Event::Key(Key::Char(']')) | Event::Key(Key::PageDown) => {
+                editor.go_to(editor.current + 1)
+            }
+            Event::Key(Key::Char('o')) => editor.onion = !editor.onion,
+            Event::Key(Key::Char('s')) => {
+                // a failed save must not quit the editor and lose the edits
+                message = match editor.save() {
+                    Ok(saved) if saved.is_empty() => "nothing to save".to_string(),
+                    Ok(saved) => format!("saved {}", saved.join(", ")),
+                    Err(error) => format!("can't save: {}", error),
+                }
+            }
+            Event::Key(Key::Char('q')) | Event::Key(Key::Ctrl('c')) => {
+                // quitting with unsaved frames needs a confirmation
+                let modified = (0..editor.frames.len()).any(|i| editor.is_modified(i));
+                if !modified || confirm_quit(&mut events, &mut stdout)? {
+                    write!(stdout, "\r\n")?;
+                    return Ok(());
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// ask to quit without saving, return true when the user answers yes
+fn confirm_quit(
+    events: &mut impl Iterator<Item = std::io::Result<Event>>,
+    stdout: &mut impl Write,
+) -> std::result::Result<bool, Box<dyn std::error::Error>> {
+    write!(stdout, "\r\nsome frames are not saved, quit anyway? (y/n)")?;
+    stdout.flush()?;
+    Ok(matches!(
+        events.next().ok_or("stdin closed")??,
+        Event::Key(Key::Char('y'))
+    ))
+}
+
+pub fn play(args: &Args) -> Result {
+    let frames = args.frames(false)?;
+    let frame = |name: &str| {
+        frames
+            .iter()
+            .find(|(frame, _)| frame == name)
+            .map(|(_, frame)| frame)
+            .ok_or_else(|| format!("unknown frame `{}`", name))
+    };
+
+    // every sequence is a list of (frame, duration)
+    let sequences: Vec<Vec<(&Frame, u32)>> = match args.manifest()? {
+        Some(manifest) => manifest
+            .sequences
+            .iter()
+            .map(|sequence| {
+                sequence
+                    .steps
+                    .iter()
+                    .map(|step| {
+                        let name = manifest.resolve(&step.frame).unwrap_or(&step.frame);
+                        Ok((frame(name)?, step.duration))
+                    })
+                    .collect::<std::result::Result<_, Box<dyn std::error::Error>>>()
+            })
+            .collect::<std::result::Result<_, _>>()?,
+        None => vec![frames
+            .iter()
+            .map(|(_, frame)| (frame, args.duration))
+            .collect()],
+    };
+
+    if args.graphics.is_some() {
+        print!("{}", termion::cursor::Save);
+    }
+    for sequence in &sequences {
+        for &(frame, duration) in sequence.iter().cycle().take(sequence.len() * args.repeat) {
+            let (drawing, lines) = draw(args, frame);
+            if lines.is_none() {
+                print!("{}", termion::cursor::Restore);
+            }
+            print!("{}", drawing);
+            stdout().flush()?;
+            std::thread::sleep(Duration::from_millis(duration as u64));
+            if let Some(lines) = lines {
+                print!("{}", termion::cursor::Up(lines));
+            }
+        }
+    }
+    match frames.first().map(|(_, frame)| draw(args, frame).1) {
+        Some(Some(lines)) => print!("{}", termion::cursor::Down(lines)),
+        Some(None) => println!(),
+        None => (),
+    }
+    Ok(())
+}
+
+/// once the key log is replayed `simulate` keeps running a little bit to see the animation
+/// going back to idle
+const TAIL_MS: u64 = 5000;
+
+/// read a key log and return the timestamps of all the key presses, sorted
+fn read_key_log(filename: &str) -> std::result::Result<Vec<u64>, Box<dyn std::error::Error>> {
+    let mut events = std::fs::read_to_string(filename)?
+        .lines()
+        .enumerate()
+        .map(|(line_nb, line)| (line_nb, line.split('#').next().unwrap().trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(line_nb, line)| {
+            let time = line.split_whitespace().next().unwrap();
+            time.parse::<u64>().map_err(|_| {
+                format!("{}:{}: invalid time `{}`", filename, line_nb + 1, time).into()
+            })
+        })
+        .collect::<std::result::Result<Vec<u64>, Box<dyn std::error::Error>>>()?;
+    events.sort_unstable();
+    Ok(events)
+}
+
+/// escape and ctrl-c quit the simulation
+fn quit_simulation(key: &std::io::Result<Key>) -> bool {
+    matches!(key, Ok(Key::Esc) | Ok(Key::Ctrl('c')))
+}
+
+pub fn simulate(args: &Args) -> Result {
+    let (manifest, key_log) = match (&args.manifest, args.files.as_slice()) {
+        (Some(manifest), []) | (None, [manifest]) => (manifest, None),
+        (Some(manifest), [key_log]) | (None, [manifest, key_log]) => {
+            (manifest, Some(read_key_log(key_log)?))
+        }
+        _ => return Err("simulate takes a manifest and an optional key log".into()),
+    };
+
+    let manifest = Manifest::load(manifest)?;
+    let animation = Animation::from_manifest(&manifest)?;
+    let frames: HashMap<String, Frame> = manifest.load_frames()?.into_iter().collect();
+
+    let mut player = animation.player();
+    let mut wpm = Wpm::new();
+
+    let stdout = stdout();
+    let mut stdout = stdout.into_raw_mode()?;
+    // the escape sequences of the arrows or the function keys are a single key
+    let mut keys = termion::async_stdin().keys();
+    write!(stdout, "{}{}", termion::clear::All, termion::cursor::Hide)?;
+
+    let start = Instant::now();
+    let mut next_event = 0;
+    let mut redraw = true;
+    let mut last_wpm = wpm.get();
+
+    loop {
+        let now = start.elapsed().as_millis() as u64;
+
+        match &key_log {
+            Some(events) => {
+                while next_event < events.len() && events[next_event] <= now {
+                    wpm.key_press(events[next_event]);
+                    player.key_press(events[next_event]);
+                    next_event += 1;
+                }
+                if next_event == events.len() && now > events.last().unwrap_or(&0) + TAIL_MS {
+                    break;
+                }
+                // let the user quit early
+                if keys.by_ref().any(|key| quit_simulation(&key)) {
+                    break;
+                }
+            }
+            None => {
+                for key in keys.by_ref() {
+                    if quit_simulation(&key) {
+                        write!(stdout, "{}", termion::cursor::Show)?;
+                        return Ok(());
+                    }
+                    wpm.key_press(now);
+                    player.key_press(now);
+                }
+            }
+        }
+
+        wpm.decay(now);
+        redraw |= player.update(now, wpm.get()) || wpm.get() != last_wpm;
+        last_wpm = wpm.get();
+
+        if redraw {
+            let name = manifest.resolve(player.frame()).unwrap();
+            write!(
+                stdout,
+                "{}{}\r\n{}time: {:>6}ms  wpm: {:>3}  state: {}  frame: {}",
+                termion::cursor::Goto(1, 1),
+                frames[name].render(args.render).replace("\n", "\r\n"),
+                termion::clear::CurrentLine,
+                now,
+                wpm.get(),
+                player.state(),
+                player.frame(),
+            )?;
+            stdout.flush()?;
+            redraw = false;
+        }
+
+        std::thread::sleep(Duration::from_millis(5));
+    }
+
+    write!(stdout, "{}\r\n", termion::cursor::Show)?;
+    Ok(())
+}
+
+pub fn encode(args: &Args) -> Result {
+    let frames = args.frames(true)?;
+    let (base_name, prefix) = args.array_names()?;
+    let encoded = encode_with_codec(
+        &frames,
+        &args.base_strategy(BaseStrategy::Suboptimal)?,
+        args.codec,
+    )?;
+
+    if let Some(out) = &args.out {
+        if args.lang.unwrap_or(Lang::C) != Lang::C {
+            return Err("encode can only write a C module".into());
+        }
+        let name = args
+            .manifest
+            .as_deref()
+            .and_then(|manifest| Path::new(manifest).file_stem()?.to_str())
+            .unwrap_or("frames");
+        let module = encoded.module(
+            name,
+            frames[0].1.dimensions(),
+            &base_name,
+            &prefix,
+            &mut args.c_emitter(),
+        )?;
+        let (header, source) = module.save(Path::new(out))?;
+        println!("{}\n{}", header.display(), source.display());
+        for merge in args.merges()? {
+            println!("{}", merge);
+        }
+        return Ok(());
+    }
+
+    let mut emitter = args.emitter(Lang::C);
+    let stdout = stdout();
+    let mut out = stdout.lock();
+    encoded.emit(&mut out, emitter.as_mut(), &base_name, &prefix)?;
+    for merge in args.merges()? {
+        emitter.comment(&mut out, &merge.to_string())?;
+    }
+    emitter.comment(
+        &mut out,
+        &format!("total array size is {} bytes", encoded.stored_size()),
+    )?;
+    emitter.finish(&mut out)?;
+    Ok(())
+}
+
+pub fn decode(args: &Args) -> Result {
+    let source = match args.files.as_slice() {
+        [source] => source,
+        _ => return Err("decode takes exactly one source file".into()),
+    };
+    let (width, height) = args.size.ok_or("decode needs the `--size` of the frames")?;
+    let out = args.out.as_deref().unwrap_or(".");
+
+    let source = std::fs::read_to_string(source)?;
+    let compressed = args.codec == Codec::Rle;
+    for (name, frame) in parse_frames(&source, width, height, compressed, args.base.as_deref())? {
+        let filename = Path::new(out).join(name.to_lowercase());
+        frame.save_to_file(filename.to_str().ok_or("invalid path")?)?;
+        println!("{} -> {}", name, filename.display());
+    }
+    Ok(())
+}
+
+pub fn base(args: &Args) -> Result {
+    let frames = args.frames(true)?;
+    let (width, height) = frames[0].1.dimensions();
+
+    let base = match args.base.as_deref().unwrap_or("suboptimal") {
+        "average" => {
+            let frames: Vec<Frame> = frames.into_iter().map(|(_, frame)| frame).collect();
+            Frame::create_from_multiple_frame(&frames)?
+        }
+        strategy => {
+            let encoded = encode_with_codec(&frames, &strategy.parse()?, args.codec)?;
+            let base = encoded
+                .base
+                .ok_or("the strategy `none` has no base frame")?;
+            Frame::new(width, height, &base)?
+        }
+    };
+
+    if args.lang.is_some() {
+        let mut emitter = args.emitter(Lang::C);
+        let stdout = stdout();
+        let mut out = stdout.lock();
+        emitter.array(
+            &mut out,
+            "BASE_FRAME",
+            &base.output_with(OutputMode::Full).data,
+        )?;
+        emitter.finish(&mut out)?;
+    } else {
+        println!("{}", base);
+    }
+    Ok(())
+}
+
+pub fn report(args: &Args) -> Result {
+    let frames = args.frames(true)?;
+    let (width, height) = frames[0].1.dimensions();
+    let buffer_len = width * height / 8;
+    println!(
+        "{} frames of {} × {}, {} bytes uncompressed",
+        frames.len(),
+        width,
+        height,
+        frames.len() * buffer_len
+    );
+
+    let mut strategies = vec![
+        BaseStrategy::None,
+        BaseStrategy::Suboptimal,
+        BaseStrategy::Best,
+    ];
+    let requested = args.base_strategy(BaseStrategy::None)?;
+    if !strategies.contains(&requested) {
+        strategies.push(requested);
+    }
+
+    println!(
+        "{:<20} {:>8} {:>8} {:>8}",
+        "base", "base", "frames", "total"
+    );
+    for strategy in &strategies {
+        let encoded = encode_with_codec(&frames, strategy, args.codec)?;
+        let base = encoded.base.as_ref().map_or(0, |base| base.len());
+        let name = match strategy {
+            BaseStrategy::None => "none".to_string(),
+            BaseStrategy::Suboptimal => "suboptimal".to_string(),
+            BaseStrategy::Best => "best".to_string(),
+            BaseStrategy::Frame(frame) => format!("frame:{}", frame),
+        };
+        println!(
+            "{:<20} {:>8} {:>8} {:>8}",
+            name,
+            base,
+            encoded.stored_size() - base,
+            encoded.stored_size()
+        );
+    }
+
+    let merges = args.merges()?;
+    if !merges.is_empty() {
+        match merges.len() {
+            1 => println!("\n1 frame is an alias of another one:"),
+            n => println!("\n{} frames are aliases of another one:", n),
+        }
+        for merge in merges {
+            println!("    {}", merge);
+        }
+    }
+    Ok(())
+}
+
+pub fn budget(args: &Args) -> Result {
+    let frames = args.frames(true)?;
+    let strategy = args.base_strategy(BaseStrategy::Suboptimal)?;
+    let animation = args
+        .manifest()?
+        .map(|manifest| Animation::from_manifest(&manifest))
+        .transpose()?;
+    let progmem = args.c_emitter().qualifier.contains("PROGMEM");
+    let mcus = if args.mcus.is_empty() {
+        profiles()
+    } else {
+        args.mcus.clone()
+    };
+    let usages = |codec: Codec| -> std::result::Result<Vec<Usage>, Box<dyn std::error::Error>> {
+        let encoded = encode_with_codec(&frames, &strategy, codec)?;
+        Ok(mcus
+            .iter()
+            .map(|mcu| estimate(&encoded, codec, animation.as_ref(), progmem, mcu.arch))
+            .collect())
+    };
+    let selected = usages(args.codec)?;
+    let other = match args.codec {
+        Codec::Rle => (Codec::Raw, "raw"),
+        Codec::Raw => (Codec::Rle, "rle"),
+    };
+    let other_usages = usages(other.0)?;
+
+    let row = |name: &str, cells: Vec<String>| {
+        println!(
+            "{:<24}{}",
+            name,
+            cells
+                .iter()
+                .map(|cell| format!("{:>11}", cell))
+                .collect::<String>()
+        )
+    };
+    let total = |usage: &Usage, flash: bool, size: usize| {
+        if flash && usage.estimated() {
+            format!("~{}", size)
+        } else {
+            size.to_string()
+        }
+    };
+    row("", mcus.iter().map(|mcu| mcu.name.clone()).collect());
+    for (memory, available, used) in &[
+        (
+            "flash",
+            mcus.iter().map(|mcu| mcu.flash).collect::<Vec<_>>(),
+            args.used.0,
+        ),
+        ("RAM", mcus.iter().map(|mcu| mcu.ram).collect(), args.used.1),
+    ] {
+        let size = |item: &Item| {
+            if *memory == "flash" {
+                item.flash
+            } else {
+                item.ram
+            }
+        };
+        println!("{}", memory);
+        for (i, item) in selected[0].items.iter().enumerate() {
+            if selected.iter().all(|usage| size(&usage.items[i]) == 0) {
+                continue;
+            }
+            row(
+                &format!("  {}", item.name),
+                selected
+                    .iter()
+                    .map(|usage| {
+                        let item = &usage.items[i];
+                        if *memory == "flash" && item.estimated {
+                            format!("~{}", size(item))
+                        } else {
+                            size(item).to_string()
+                        }
+                    })
+                    .collect(),
+            );
+        }
+        let needed = |usage: &Usage| match *memory {
+            "flash" => usage.flash(),
+            _ => usage.ram(),
+        };
+        row(
+            "  total",
+            selected
+                .iter()
+                .map(|usage| total(usage, *memory == "flash", needed(usage)))
+                .collect(),
+        );
+        row(
+            &format!("  total with {}", other.1),
+            other_usages
+                .iter()
+                .map(|usage| total(usage, *memory == "flash", needed(usage)))
+                .collect(),
+        );
+        row(
+            "  free",
+            available
+                .iter()
+                .map(|available| available.saturating_sub(*used).to_string())
+                .collect(),
+        );
+    }
+
+    // the time taken by the decoder in the OLED task, with and without base frame
+    let diffed = match strategy {
+        BaseStrategy::None => BaseStrategy::Suboptimal,
+        strategy => strategy,
+    };
+    row(
+        &format!("decoding at {} MHz", AVR_CLOCK / 1_000_000),
+        vec![
+            "size".to_string(),
+            "average".to_string(),
+            "worst".to_string(),
+        ],
+    );
+    for (codec, name) in &[(Codec::Rle, "rle"), (Codec::Raw, "raw")] {
+        for (strategy, mode) in &[(&BaseStrategy::None, ""), (&diffed, ", diffed")] {
+            let encoded = encode_with_codec(&frames, strategy, *codec)?;
+            let (average, worst) = AVR.decode_frames(&encoded, *codec);
+            row(
+                &format!("  {}{}", name, mode),
+                vec![
+                    encoded.stored_size().to_string(),
+                    format!("{} µs", micros(average, AVR_CLOCK)),
+                    format!("{} µs", micros(worst, AVR_CLOCK)),
+                ],
+            );
+        }
+    }
+
+    for (mcu, usage) in mcus.iter().zip(&selected) {
+        for warning in mcu.check(usage, args.used) {
+            eprintln!("warning: {}", warning);
+        }
+    }
+    Ok(())
+}
+
+pub fn convert(args: &Args) -> Result {
+    let mut emitter = args.emitter(Lang::C);
+    let stdout = stdout();
+    let mut out = stdout.lock();
+    for (name, frame) in args.frames(true)? {
+        let data = args.codec.encode(&frame.output_with(OutputMode::Full).data);
+        emitter.array(&mut out, &name.to_uppercase(), &data)?;
+    }
+    emitter.finish(&mut out)?;
+    Ok(())
+}
+
+pub fn lossy(args: &Args) -> Result {
+    // the merged frames are encoded, but the altered pixels are counted from the originals
+    let frames = args.frames(true)?;
+    let originals = args.frames_with(true, false)?;
+    let budget = args
+        .budget
+        .ok_or("lossy needs a `--budget PIXELS` or a `--max-size BYTES`")?;
+    if args.codec != Codec::Rle {
+        return Err("lossy only works with the rle codec".into());
+    }
+    let strategy = args.base_strategy(BaseStrategy::Suboptimal)?;
+    let lossless = encode_with_codec(&frames, &strategy, Codec::Rle)?;
+    let lossy = kyria_oled::lossy::encode_lossy(&frames, &strategy, budget)?;
+    let altered: Vec<(&str, usize)> = originals
+        .iter()
+        .zip(&lossy.frames)
+        .map(|((name, original), (_, frame))| {
+            (name.as_str(), count_different_pixels(original, frame))
+        })
+        .collect();
+
+    if args.lang.is_some() {
+        let (base_name, prefix) = args.array_names()?;
+        let mut emitter = args.emitter(Lang::C);
+        let stdout = stdout();
+        let mut out = stdout.lock();
+        lossy
+            .encoded
+            .emit(&mut out, emitter.as_mut(), &base_name, &prefix)?;
+        for (name, pixels) in altered.iter().filter(|(_, pixels)| *pixels > 0) {
+            emitter.comment(&mut out, &format!("{}: {} pixels altered", name, pixels))?;
+        }
+        emitter.comment(
+            &mut out,
+            &format!("total array size is {} bytes", lossy.encoded.stored_size()),
+        )?;
+        emitter.finish(&mut out)?;
+        return Ok(());
+    }
+
+    for (((name, original), (_, frame)), (_, pixels)) in
+        originals.iter().zip(&lossy.frames).zip(&altered)
+    {
+        if *pixels > 0 {
+            println!(
+                "{}: {} pixels altered, `++` is an added pixel and `--` a removed one",
+                name, pixels
+            );
+            println!("{}", pixel_diff(original, frame));
+        }
+    }
+    println!(
+        "{:<12} {:>8} {:>8} {:>8}",
+        "frame", "lossless", "lossy", "pixels"
+    );
+    for (((name, lossless), (_, lossy)), (_, pixels)) in lossless
+        .frames
+        .iter()
+        .zip(&lossy.encoded.frames)
+        .zip(&altered)
+    {
+        println!(
+            "{:<12} {:>8} {:>8} {:>8}",
+            name,
+            lossless.len(),
+            lossy.len(),
+            pixels
+        );
+    }
+    println!(
+        "{:<12} {:>8} {:>8}    at most {} pixels per frame",
+        "total",
+        lossless.total_size(),
+        lossy.encoded.total_size(),
+        lossy.pixels
+    );
+    Ok(())
+}
+
+pub fn verify(args: &Args) -> Result {
+    let frames = args.frames(true)?;
+    let encoded = match &args.source {
+        Some(source) => {
+            let arrays = parse_arrays(&std::fs::read_to_string(source)?)?;
+            let find = |name: &str| {
+                arrays
+                    .iter()
+                    .find(|array| array.name == name)
+                    .map(|array| array.data.clone())
+            };
+            let (base_name, prefix) = args.array_names()?;
+            let (width, height) = frames[0].1.dimensions();
+
+            Encoded {
+                buffer_len: width * height / 8,
+                base: find(&base_name),
+                frames: frames
+                    .iter()
+                    .map(|(name, _)| {
+                        let array = format!("{}{}", prefix, name.to_uppercase());
+                        let data = find(&array)
+                            .ok_or_else(|| format!("{}: there is no array `{}`", source, array))?;
+                        Ok((name.clone(), data))
+                    })
+                    .collect::<std::result::Result<_, Box<dyn std::error::Error>>>()?,
+            }
+        }
+        None => encode_with_codec(
+            &frames,
+            &args.base_strategy(BaseStrategy::Suboptimal)?,
+            args.codec,
+        )?,
+    };
+
+    let mismatches = kyria_oled::verify::verify(&frames, &encoded, args.codec);
+    for mismatch in &mismatches {
+        println!("{}", mismatch);
+    }
+    if !mismatches.is_empty() {
+        return Err(format!(
+            "{} of the {} frames don't match",
+            mismatches.len(),
+            frames.len()
+        )
+        .into());
+    }
+    println!("the {} frames match", frames.len());
+    Ok(())
+}
+
+pub fn gen(args: &Args) -> Result {
+    if args.lang.unwrap_or(Lang::C) != Lang::C {
+        return Err("gen can only generate C".into());
+    }
+    let manifest = match (&args.manifest, args.files.as_slice()) {
+        (Some(manifest), []) | (None, [manifest]) => manifest,
+        _ => return Err("gen takes exactly one manifest".into()),
+    };
+    let path = manifest;
+    let mut manifest = Manifest::load(path)?;
+    if let Some(tolerance) = args.tolerance {
+        manifest.tolerance = tolerance;
+    }
+    // on stderr to not mix it with the generated code
+    for merge in manifest.merges()? {
+        eprintln!("{}", merge);
+    }
+    let name = Path::new(path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| format!("can't name the module of `{}`", path))?;
+    let module = match args.target {
+        Target::C => return manifest.generate_c(&mut stdout().lock()),
+        Target::Qmk => kyria_oled::qmk::generate_module(&manifest, name)?,
+        Target::Zmk(format) => kyria_oled::zmk::generate_module(&manifest, name, format)?,
+    };
+    let (header, source) = module.save(Path::new(args.out.as_deref().unwrap_or(".")))?;
+    println!("{}\n{}", header.display(), source.display());
+    Ok(())
+}
@@ -0,0 +1,184 @@
+mod args;
+mod commands;
+
+use args::Args;
+use std::path::Path;
+
+const USAGE: &str = "usage: kyria-oled <command> [files] [flags]
+
+commands:
+    view       browse frames with the left and right arrows, quit with q
+    edit       edit the pixels of frame files, with a selection, copy and paste between frames,
+               undo and the previous frame shown under the current one, the keys are shown
+    play       play the frames, or all the sequences of a manifest, in the terminal
+    simulate   run the animation of a manifest driven by the keys you type, or by a key log
+               given after the manifest, quit with escape or ctrl-c. A key log contains one
+               key press per line, the time in ms since the start followed by an optional key
+               name, like `130 e`
+    encode     print the frames as arrays, diffed against a base frame, the identical frames
+               are aliases. With `--out` a C module with the frame tables is written instead
+    decode     extract the frames from the arrays of a C or rust source file
+    base       display the base frame computed from the frames
+    report     compare the size of the frames with every base strategy
+    budget     estimate the flash and the RAM used by the frames, and by the animation of a
+               manifest, on every `--mcu`, and warn when they don't fit. The time taken to
+               decode a frame on a 16 MHz AVR is shown for every codec, with and without base
+    convert    print every frame as an array, without base
+    gen        print the C code of a whole animation manifest, or write it as a QMK or ZMK
+               module named after the manifest with `--target`
+    lossy      flip a few pixels of every frame for smaller arrays, show the altered pixels and
+               the sizes, or print the arrays with `--lang`
+    verify     decode the arrays like the firmware and compare them with the frames, the
+               arrays are encoded again unless a generated `--source` is given
+    help       print this message
+
+flags:
+    --size WxH          the dimensions of the frames, required by `decode`
+    --codec rle|raw     how the frames are stored (rle)
+    --base STRATEGY     none, suboptimal, best or frame:NAME, `base` also accepts average.
+                        `decode` takes the name of the base array
+    --lang LANG         the language of the arrays: c, rust, python or bin for a binary blob
+                        with an index (c), `base` prints an array when it's set
+    --prefix PREFIX     prepended to the name of the arrays
+    --hex               write the values of the arrays in hexadecimal
+    --width N           wrap the arrays before N columns (80)
+    --qualifier Q       written before the name of the C arrays (PROGMEM)
+    --section NAME      put the C arrays in a linker section
+    --target TARGET     what `gen` generates: c, qmk, zmk for LVGL images, or zmk-raw for
+                        the compressed frames written with `display_write` (c)
+    --manifest FILE     use the frames of an animation manifest instead of frame files
+    --out DIR           where `decode` writes the frames and `gen` or `encode` the module (.)
+    --source FILE       the C or rust file generated by `encode` or `gen` to `verify`
+    --tolerance PIXELS  merge the frames differing by at most PIXELS from a previous frame,
+                        overrides the tolerance of the manifest (0, only identical frames)
+    --budget PIXELS     the number of pixels `lossy` can flip in every frame
+    --max-size BYTES    or the total size `lossy` must reach, the pixel budget is searched
+    --mcu MCU           32u4, rp2040, stm32f303, nrf52840 or NAME:ARCH:FLASH:RAM with the
+                        architecture avr or arm, sizes can end with k or M (all the profiles)
+    --used FLASH:RAM    the memory used by the rest of the firmware, for `budget` (0:0)
+    --render STYLE      how `view`, `play` and `simulate` draw the frames: blocks, half for
+                        half blocks, braille, or inverse for ANSI colours (blocks)
+    --graphics PROTOCOL show the frames of `view` and `play` as images with the sixel or kitty
+                        graphics protocol, auto guesses it and falls back to half blocks
+    --scale N           the size of a pixel of the frames in pixels of the images (2)
+    --palette PALETTE   the colour of the pixels of the images: white or blue (white)
+    --duration MS       the duration of a frame played without manifest (200)
+    --repeat N          how many times `play` plays every sequence (2)
+
+presets, the old binaries now running a command, run them as the command or through a link to
+kyria-oled named after them:
+    display_frame               view
+    display_average_frame       base --base average
+    display_suboptimal_frame    base --base suboptimal
+    rusty                       base --base suboptimal --lang c rusty_frames/rust1 … rust6
+    generate_c                  encode --base suboptimal
+    import_frames               decode
+    bongo_cat                   gen bongo_cat.manifest
+    bongo_cat_print             play --manifest bongo_cat.manifest
+
+The files of the presets are read in the working directory, run them from the crate directory.";
+
+/// the old binaries, as their name, their arguments and the files of the crate they read. The
+/// files are relative to the working directory, so they are found in the crate directory
+/// wherever the binary is installed. The arguments given to a preset are appended to the files
+const PRESETS: &[(&str, &[&str], &[&str])] = &[
+    ("display_frame", &["view"], &[]),
+    ("display_average_frame", &["base", "--base", "average"], &[]),
+    (
+        "display_suboptimal_frame",
+        &["base", "--base", "suboptimal"],
+        &[],
+    ),
+    (
+        "rusty",
+        &["base", "--base", "suboptimal", "--lang", "c"],
+        &[
+            "rusty_frames/rust1",
+            "rusty_frames/rust2",
+            "rusty_frames/rust3",
+            "rusty_frames/rust4",
+            "rusty_frames/rust5",
+            "rusty_frames/rust6",
+        ],
+    ),
+    ("generate_c", &["encode", "--base", "suboptimal"], &[]),
+    ("import_frames", &["decode"], &[]),
+    ("bongo_cat", &["gen"], &["bongo_cat.manifest"]),
+    (
+        "bongo_cat_print",
+        &["play", "--manifest"],
+        &["bongo_cat.manifest"],
+    ),
+];
+
+/// the arguments of the preset `name`, if there's one
+fn preset(name: Option<&str>) -> Option<Result<Vec<String>, Box<dyn std::error::Error>>> {
+    let (name, args, files) = PRESETS
+        .iter()
+        .find(|(preset, _, _)| Some(*preset) == name)?;
+    if let Some(file) = files.iter().find(|file| !Path::new(file).exists()) {
+        return Some(Err(format!(
+            "the preset `{}` reads `{}` in the working directory, run it from the kyria_oled directory",
+            name, file
+        )
+        .into()));
+    }
+    Some(Ok(args
+        .iter()
+        .chain(files.iter())
+        .map(|arg| arg.to_string())
+        .collect()))
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("kyria-oled: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args: Vec<String> = std::env::args().collect();
+    // a link named after a preset runs it, otherwise the first argument can be a preset
+    let program = args
+        .first()
+        .and_then(|program| Path::new(program).file_stem())
+        .and_then(|stem| stem.to_str());
+    match preset(program) {
+        Some(preset) => {
+            args.splice(..1, preset?);
+        }
+        None => {
+            args.remove(0);
+            if let Some(preset) = preset(args.first().map(String::as_str)) {
+                args.splice(..1, preset?);
+            }
+        }
+    }
+
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), Args::parse(args)?),
+        None => return Err(USAGE.into()),
+    };
+
+    match command {
+        "view" => commands::view(&args),
+        "edit" => commands::edit(&args),
+        "play" => commands::play(&args),
+        "simulate" => commands::simulate(&args),
+        "encode" => commands::encode(&args),
+        "decode" => commands::decode(&args),
+        "base" => commands::base(&args),
+        "report" => commands::report(&args),
+        "budget" => commands::budget(&args),
+        "convert" => commands::convert(&args),
+        "gen" => commands::gen(&args),
+        "lossy" => commands::lossy(&args),
+        "verify" => commands::verify(&args),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        command => Err(format!("unknown command `{}`\n\n{}", command, USAGE).into()),
+    }
+}
@@ -0,0 +1,347 @@
+//! estimate the flash and the RAM used by a generated animation, and compare them with the
+//! memory of a microcontroller.
+//!
+//! The sizes of the arrays, of the sequence tables and of the frame buffer are exact. The size
+//! of the code is a rough estimate of what gcc generates with `-Os`, it changes with the
+//! compiler and its flags, so it's shown with a `~`.
+
+use crate::animation::Animation;
+use crate::generator::{Codec, Encoded};
+
+/// the architecture of a microcontroller, it decides the size of the pointers and of the code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arch {
+    /// 8 bit AVR, the constants are copied in RAM unless they are `PROGMEM`
+    Avr,
+    /// 32 bit ARM Cortex-M, the constants stay in flash
+    Arm,
+}
+
+impl Arch {
+    fn pointer_size(self) -> usize {
+        match self {
+            Arch::Avr => 2,
+            Arch::Arm => 4,
+        }
+    }
+}
+
+impl std::str::FromStr for Arch {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "avr" => Ok(Arch::Avr),
+            "arm" => Ok(Arch::Arm),
+            s => Err(format!("unknown architecture `{}`, expected `avr` or `arm`", s).into()),
+        }
+    }
+}
+
+/// the memory of a microcontroller available to the firmware
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mcu {
+    pub name: String,
+    pub arch: Arch,
+    /// the flash left by the usual bootloader
+    pub flash: usize,
+    pub ram: usize,
+}
+
+/// the microcontrollers of the usual keyboards
+pub fn profiles() -> Vec<Mcu> {
+    let mcu = |name: &str, arch, flash, ram| Mcu {
+        name: name.to_string(),
+        arch,
+        flash,
+        ram,
+    };
+    vec![
+        // the 4 KiB Caterina bootloader of the Pro Micro
+        mcu("32u4", Arch::Avr, 28 * 1024, 2560),
+        // the usual 2 MiB external flash, the bootloader is in ROM
+        mcu("rp2040", Arch::Arm, 2 * 1024 * 1024, 264 * 1024),
+        // the STM32F303CC of the Proton C, without the CCM RAM
+        mcu("stm32f303", Arch::Arm, 256 * 1024, 40 * 1024),
+        // the application area of the nice!nano, between the reserved area and the bootloader
+        mcu("nrf52840", Arch::Arm, 0xf4000 - 0x26000, 256 * 1024),
+    ]
+}
+
+impl std::str::FromStr for Mcu {
+    type Err = Box<dyn std::error::Error>;
+
+    /// the name of a profile, or `NAME:ARCH:FLASH:RAM` for another microcontroller
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(mcu) = profiles().into_iter().find(|mcu| mcu.name == s) {
+            return Ok(mcu);
+        }
+        match s.split(':').collect::<Vec<_>>()[..] {
+            [name, arch, flash, ram] => Ok(Mcu {
+                name: name.to_string(),
+                arch: arch.parse()?,
+                flash: parse_bytes(flash)?,
+                ram: parse_bytes(ram)?,
+            }),
+            _ => Err(format!(
+                "unknown microcontroller `{}`, expected {} or `NAME:ARCH:FLASH:RAM`",
+                s,
+                profiles()
+                    .iter()
+                    .map(|mcu| format!("`{}`", mcu.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .into()),
+        }
+    }
+}
+
+/// parse a number of bytes, with an optional `k` (KiB) or `M` (MiB) suffix
+pub fn parse_bytes(s: &str) -> Result<usize, Box<dyn std::error::Error>> {
+    let (number, unit) = match s.strip_suffix(|c| c == 'k' || c == 'K') {
+        Some(number) => (number, 1024),
+        None => match s.strip_suffix('M') {
+            Some(number) => (number, 1024 * 1024),
+            None => (s, 1),
+        },
+    };
+    number
+        .parse::<usize>()
+        .map(|number| number * unit)
+        .map_err(|_| format!("invalid size `{}`", s).into())
+}
+
+/// something using flash or RAM
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    pub name: String,
+    pub flash: usize,
+    pub ram: usize,
+    /// the flash is a rough estimate of the size of some code
+    pub estimated: bool,
+}
+
+/// the memory used by an animation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Usage {
+    pub items: Vec<Item>,
+}
+
+impl Usage {
+    pub fn flash(&self) -> usize {
+        self.items.iter().map(|item| item.flash).sum()
+    }
+
+    pub fn ram(&self) -> usize {
+        self.items.iter().map(|item| item.ram).sum()
+    }
+
+    /// the total flash includes an estimate
+    pub fn estimated(&self) -> bool {
+        self.items.iter().any(|item| item.estimated)
+    }
+}
+
+/// the approximate size of the code decoding a frame in the frame buffer, undiffing it when
+/// there's a base frame
+pub fn decoder_size(codec: Codec, base: bool, arch: Arch) -> usize {
+    let (decode, undiff) = match (codec, arch) {
+        (Codec::Rle, Arch::Avr) => (110, 40),
+        (Codec::Raw, Arch::Avr) => (30, 40),
+        (Codec::Rle, Arch::Arm) => (70, 24),
+        (Codec::Raw, Arch::Arm) => (16, 24),
+    };
+    if base {
+        decode + undiff
+    } else {
+        decode
+    }
+}
+
+/// the approximate size of the code of `Animation::generate_c` and of the render function
+fn state_machine_size(animation: &Animation, arch: Arch) -> usize {
+    let (code, state, transition) = match arch {
+        Arch::Avr => (360, 20, 30),
+        Arch::Arm => (240, 12, 20),
+    };
+    code + state * animation.states.len() + transition * animation.transitions.len()
+}
+
+/// the sequence tables of `Manifest::arrays_c` and `Animation::generate_c`, they are not
+/// `PROGMEM`
+fn tables_size(animation: &Animation, arch: Arch) -> usize {
+    let pointer = arch.pointer_size();
+    let steps: usize = animation.states.iter().map(|state| state.steps.len()).sum();
+    // <STATE>_SEQUENCE, <STATE>_SIZES and <STATE>_DURATIONS
+    let sequences = steps * (pointer + 2 + 2);
+    // ANIMATION_SEQUENCES, ANIMATION_SIZES, ANIMATION_DURATIONS and ANIMATION_LENGTHS
+    sequences + animation.states.len() * (3 * pointer + 1)
+}
+
+/// the flash and RAM used by the frames encoded with `codec`, and by the state machine of
+/// `animation` if there's one. `progmem` tells if the arrays are `PROGMEM`, which only matters
+/// on AVR
+pub fn estimate(
+    encoded: &Encoded,
+    codec: Codec,
+    animation: Option<&Animation>,
+    progmem: bool,
+    arch: Arch,
+) -> Usage {
+    // without PROGMEM the constants are copied from the flash to the RAM at startup
+    let in_ram = |size: usize, progmem: bool| match arch {
+        Arch::Avr if !progmem => size,
+        _ => 0,
+    };
+    let arrays = encoded.stored_size();
+    let codec_name = match codec {
+        Codec::Rle => "rle",
+        Codec::Raw => "raw",
+    };
+
+    let mut items = vec![Item {
+        name: "frame arrays".to_string(),
+        flash: arrays,
+        ram: in_ram(arrays, progmem),
+        estimated: false,
+    }];
+    if let Some(animation) = animation {
+        let tables = tables_size(animation, arch);
+        items.push(Item {
+            name: "sequence tables".to_string(),
+            flash: tables,
+            ram: in_ram(tables, false),
+            estimated: false,
+        });
+    }
+    items.push(Item {
+        name: format!("decoder ({})", codec_name),
+        flash: decoder_size(codec, encoded.base.is_some(), arch),
+        ram: 0,
+        estimated: true,
+    });
+    if let Some(animation) = animation {
+        items.push(Item {
+            name: "state machine".to_string(),
+            flash: state_machine_size(animation, arch),
+            // the state, the step, the 2 timers and 3 flags
+            ram: 1 + 1 + 4 + 4 + 3,
+            estimated: true,
+        });
+    }
+    items.push(Item {
+        name: "frame buffer".to_string(),
+        flash: 0,
+        ram: encoded.buffer_len,
+        estimated: false,
+    });
+    Usage { items }
+}
+
+impl Mcu {
+    /// the warnings when `usage` doesn't fit next to the `used` (flash, RAM) of the rest of the
+    /// firmware
+    pub fn check(&self, usage: &Usage, used: (usize, usize)) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let (used_flash, used_ram) = used;
+        for (memory, needed, used, available) in &[
+            ("flash", usage.flash(), used_flash, self.flash),
+            ("RAM", usage.ram(), used_ram, self.ram),
+        ] {
+            let free = available.saturating_sub(*used);
+            if needed > &free {
+                warnings.push(format!(
+                    "the animation needs {} bytes of {} on the {}, only {} are free",
+                    needed, memory, self.name, free
+                ));
+            }
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::Manifest;
+
+    #[test]
+    fn test_parse_mcu() {
+        let mcu: Mcu = "32u4".parse().unwrap();
+        assert_eq!(mcu.arch, Arch::Avr);
+        assert_eq!(mcu.flash, 28672);
+
+        let mcu: Mcu = "custom:arm:64k:8192".parse().unwrap();
+        assert_eq!(mcu.name, "custom");
+        assert_eq!((mcu.flash, mcu.ram), (65536, 8192));
+        assert_eq!(parse_bytes("2M").unwrap(), 2 * 1024 * 1024);
+
+        assert!("atmega".parse::<Mcu>().is_err());
+        assert!("custom:z80:64k:8k".parse::<Mcu>().is_err());
+        assert!("custom:avr:lots:8k".parse::<Mcu>().is_err());
+    }
+
+    #[test]
+    fn test_estimate() {
+        let path = format!("{}/bongo_cat.manifest", env!("CARGO_MANIFEST_DIR"));
+        let manifest = Manifest::load(&path).unwrap();
+        let encoded = manifest.encode().unwrap();
+        let animation = Animation::from_manifest(&manifest).unwrap();
+
+        let avr = estimate(&encoded, Codec::Rle, Some(&animation), true, Arch::Avr);
+        let flash = |usage: &Usage, name: &str| {
+            usage
+                .items
+                .iter()
+                .find(|item| item.name == name)
+                .unwrap()
+                .flash
+        };
+        assert_eq!(flash(&avr, "frame arrays"), encoded.stored_size());
+        assert_eq!(
+            flash(&avr, "decoder (rle)"),
+            decoder_size(Codec::Rle, true, Arch::Avr)
+        );
+        assert!(avr.estimated());
+
+        // the tables are copied in RAM on AVR only, and the pointers are bigger on ARM
+        let tables = tables_size(&animation, Arch::Avr);
+        assert_eq!(avr.ram(), tables + 13 + encoded.buffer_len);
+        let arm = estimate(&encoded, Codec::Rle, Some(&animation), true, Arch::Arm);
+        assert_eq!(arm.ram(), 13 + encoded.buffer_len);
+        assert!(flash(&arm, "sequence tables") > tables);
+
+        // without PROGMEM the arrays are in RAM too
+        let ram = estimate(&encoded, Codec::Rle, Some(&animation), false, Arch::Avr);
+        assert_eq!(ram.ram(), avr.ram() + encoded.stored_size());
+
+        // without animation there's only the frames
+        let frames = estimate(&encoded, Codec::Rle, None, true, Arch::Avr);
+        assert_eq!(frames.items.len(), 3);
+    }
+
+    #[test]
+    fn test_check() {
+        let mcu: Mcu = "32u4".parse().unwrap();
+        let usage = Usage {
+            items: vec![Item {
+                name: "frame arrays".to_string(),
+                flash: 4000,
+                ram: 600,
+                estimated: false,
+            }],
+        };
+        assert!(mcu.check(&usage, (0, 0)).is_empty());
+        assert!(mcu.check(&usage, (24672, 1960)).is_empty());
+
+        let warnings = mcu.check(&usage, (25000, 2000));
+        assert_eq!(
+            warnings,
+            vec![
+                "the animation needs 4000 bytes of flash on the 32u4, only 3672 are free",
+                "the animation needs 600 bytes of RAM on the 32u4, only 560 are free",
+            ]
+        );
+    }
+}
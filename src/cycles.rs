@@ -0,0 +1,149 @@
+//! estimate the time the generated decoder takes to decode a frame on AVR.
+//!
+//! The decoder of `decoder_c` is walked on the encoded data like the firmware runs it, every
+//! step of its loops costs the cycles of the instructions avr-gcc generates for it with `-Os`.
+//! The costs are approximations, but the counts of loop iterations are exact, so the estimate
+//! is good enough to compare the codecs and to know if the OLED task adds latency to the scan.
+
+use crate::generator::{Codec, Encoded};
+
+/// the clock of the usual AVR keyboards, like the 32u4 of the Pro Micro
+pub const AVR_CLOCK: usize = 16_000_000;
+
+/// the cycles of every step of the decoder loops
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleModel {
+    /// calling the decoder and returning
+    pub call: usize,
+    /// reading a control byte with `pgm_read_byte` and branching on its kind
+    pub control: usize,
+    /// reading a literal byte and writing it, with the bound check
+    pub literal: usize,
+    /// reading the value of a run
+    pub run: usize,
+    /// writing a byte of a run, with the bound check
+    pub run_byte: usize,
+    /// copying a byte of the raw codec from the flash
+    pub copy: usize,
+    /// undiffing a byte of the frame buffer against the base frame
+    pub undiff: usize,
+}
+
+/// the costs of `decoder_c` reading `PROGMEM` arrays: a `pgm_read_byte` is an `lpm` of 3 cycles
+/// plus the address setup, the 16 bit loop counters and bound checks are 3 or 4 cycles
+pub const AVR: CycleModel = CycleModel {
+    call: 20,
+    control: 14,
+    literal: 15,
+    run: 6,
+    run_byte: 10,
+    copy: 8,
+    undiff: 14,
+};
+
+impl CycleModel {
+    /// the cycles taken to decode `data` in a frame buffer of `buffer_len` bytes, `base` tells
+    /// if the frame is undiffed against a base frame
+    pub fn decode(&self, codec: Codec, data: &[u8], buffer_len: usize, base: bool) -> usize {
+        let mut cycles = self.call;
+        match codec {
+            Codec::Raw => cycles += self.copy * data.len().min(buffer_len),
+            Codec::Rle => {
+                let mut pos = 0;
+                let mut i = 0;
+                while i < data.len() {
+                    let control = data[i];
+                    let n = (control & 0x7f) as usize;
+                    cycles += self.control;
+                    if control & 0x80 != 0 {
+                        // the literals are read even past the end of the buffer
+                        let n = n.min(data.len() - i - 1);
+                        cycles += self.literal * n;
+                        pos += n;
+                        i += n;
+                    } else {
+                        let n = n.min(buffer_len.saturating_sub(pos));
+                        cycles += self.run + self.run_byte * n;
+                        pos += n;
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+        }
+        if base {
+            cycles += self.undiff * buffer_len;
+        }
+        cycles
+    }
+
+    /// the (average, worst) cycles taken to decode the frames of `encoded`
+    pub fn decode_frames(&self, encoded: &Encoded, codec: Codec) -> (usize, usize) {
+        let cycles: Vec<usize> = encoded
+            .frames
+            .iter()
+            .map(|(_, data)| self.decode(codec, data, encoded.buffer_len, encoded.base.is_some()))
+            .collect();
+        let total: usize = cycles.iter().sum();
+        (
+            total / cycles.len().max(1),
+            cycles.iter().copied().max().unwrap_or(0),
+        )
+    }
+}
+
+/// the microseconds taken by `cycles` cycles at `clock` Hz
+pub fn micros(cycles: usize, clock: usize) -> usize {
+    (cycles * 1_000_000).div_ceil(clock)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{encode_with_codec, BaseStrategy};
+
+    #[test]
+    fn test_decode() {
+        let zeros = [0u8; 512];
+        let rle = crate::compress(&zeros);
+        assert_eq!(
+            AVR.decode(Codec::Rle, &rle, 512, false),
+            AVR.call + rle.len() / 2 * (AVR.control + AVR.run) + 512 * AVR.run_byte
+        );
+        assert_eq!(
+            AVR.decode(Codec::Raw, &zeros, 512, false),
+            AVR.call + 512 * AVR.copy
+        );
+
+        // literals cost more than runs, the undiff is paid on every byte
+        let noise: Vec<u8> = (0..=255).collect();
+        let literals = crate::compress(&noise);
+        assert!(
+            AVR.decode(Codec::Rle, &literals, 256, false)
+                > AVR.decode(Codec::Raw, &noise, 256, false)
+        );
+        assert_eq!(
+            AVR.decode(Codec::Rle, &literals, 256, true),
+            AVR.decode(Codec::Rle, &literals, 256, false) + 256 * AVR.undiff
+        );
+
+        // the runs stop at the end of the buffer like in the C decoder
+        assert_eq!(
+            AVR.decode(Codec::Rle, &[100, 0], 10, false),
+            AVR.call + AVR.control + AVR.run + 10 * AVR.run_byte
+        );
+        assert_eq!(AVR.decode(Codec::Rle, &[], 10, false), AVR.call);
+    }
+
+    #[test]
+    fn test_decode_frames() {
+        let frames = crate::bongo_cat(&["idle1", "tap1"]);
+        let encoded = encode_with_codec(&frames, &BaseStrategy::None, Codec::Rle).unwrap();
+        let (average, worst) = AVR.decode_frames(&encoded, Codec::Rle);
+        assert!(average <= worst);
+        assert!(worst > encoded.buffer_len * AVR.run_byte);
+
+        assert_eq!(micros(16, AVR_CLOCK), 1);
+        assert_eq!(micros(17, AVR_CLOCK), 2);
+    }
+}
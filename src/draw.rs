@@ -0,0 +1,131 @@
+use crate::Frame;
+
+/// the drawing primitives, everything drawn outside of the frame is clipped
+impl Frame {
+    /// draw a line between two points, both included, with the Bresenham algorithm
+    pub fn draw_line(&mut self, x0: isize, y0: isize, x1: isize, y1: isize, on: bool) {
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+        let (mut x, mut y) = (x0, y0);
+        let mut error = dx + dy;
+
+        loop {
+            self.set_clipped(x, y, on);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// draw the outline of a rectangle with its top left corner at (`x`, `y`)
+    pub fn draw_rect(&mut self, x: isize, y: isize, width: usize, height: usize, on: bool) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let (right, bottom) = (x + width as isize - 1, y + height as isize - 1);
+        self.draw_line(x, y, right, y, on);
+        self.draw_line(x, bottom, right, bottom, on);
+        self.draw_line(x, y, x, bottom, on);
+        self.draw_line(right, y, right, bottom, on);
+    }
+
+    /// draw a filled rectangle with its top left corner at (`x`, `y`)
+    pub fn fill_rect(&mut self, x: isize, y: isize, width: usize, height: usize, on: bool) {
+        for y in y..y + height as isize {
+            for x in x..x + width as isize {
+                self.set_clipped(x, y, on);
+            }
+        }
+    }
+
+    /// draw the outline of a circle with the midpoint algorithm
+    pub fn draw_circle(&mut self, cx: isize, cy: isize, radius: usize, on: bool) {
+        let (mut x, mut y) = (radius as isize, 0);
+        let mut error = 1 - x;
+
+        while x >= y {
+            for &(px, py) in &[(x, y), (y, x)] {
+                self.set_clipped(cx + px, cy + py, on);
+                self.set_clipped(cx - px, cy + py, on);
+                self.set_clipped(cx + px, cy - py, on);
+                self.set_clipped(cx - px, cy - py, on);
+            }
+            y += 1;
+            if error < 0 {
+                error += 2 * y + 1;
+            } else {
+                x -= 1;
+                error += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// draw the outline of an ellipse with the midpoint algorithm
+    pub fn draw_ellipse(&mut self, cx: isize, cy: isize, rx: usize, ry: usize, on: bool) {
+        // a flat ellipse is a line, the loops below would only draw its ends
+        if rx == 0 || ry == 0 {
+            let (rx, ry) = (rx as isize, ry as isize);
+            self.draw_line(cx - rx, cy - ry, cx + rx, cy + ry, on);
+            return;
+        }
+        let (rx2, ry2) = ((rx * rx) as isize, (ry * ry) as isize);
+        let (mut x, mut y) = (0, ry as isize);
+        let plot = |frame: &mut Frame, x: isize, y: isize| {
+            frame.set_clipped(cx + x, cy + y, on);
+            frame.set_clipped(cx - x, cy + y, on);
+            frame.set_clipped(cx + x, cy - y, on);
+            frame.set_clipped(cx - x, cy - y, on);
+        };
+
+        // first region, the slope is less than 1
+        // all the decisions are multiplied by 4 to stay in integers
+        let mut decision = 4 * ry2 - 4 * rx2 * ry as isize + rx2;
+        while ry2 * x < rx2 * y {
+            plot(self, x, y);
+            if decision < 0 {
+                decision += 4 * ry2 * (2 * x + 3);
+            } else {
+                decision += 4 * ry2 * (2 * x + 3) - 8 * rx2 * (y - 1);
+                y -= 1;
+            }
+            x += 1;
+        }
+
+        // second region, the slope is greater than 1
+        let mut decision =
+            ry2 * (2 * x + 1) * (2 * x + 1) + 4 * rx2 * (y - 1) * (y - 1) - 4 * rx2 * ry2;
+        while y >= 0 {
+            plot(self, x, y);
+            if decision > 0 {
+                decision += 4 * rx2 * (3 - 2 * y);
+            } else {
+                decision += 8 * ry2 * (x + 1) + 4 * rx2 * (3 - 2 * y);
+                x += 1;
+            }
+            y -= 1;
+        }
+    }
+
+    /// draw the outline of a polygon, the last point is linked to the first one
+    pub fn draw_polygon(&mut self, points: &[(isize, isize)], on: bool) {
+        for (i, &(x0, y0)) in points.iter().enumerate() {
+            let (x1, y1) = points[(i + 1) % points.len()];
+            self.draw_line(x0, y0, x1, y1, on);
+        }
+    }
+
+    fn set_clipped(&mut self, x: isize, y: isize, on: bool) {
+        if x >= 0 && y >= 0 {
+            self.set(x as usize, y as usize, on);
+        }
+    }
+}
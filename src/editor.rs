@@ -0,0 +1,392 @@
+//! the pixel editor of the frame files, without the terminal so it can be tested.
+//!
+//! The editor has a cursor, a rectangular selection between an anchor and the cursor, a
+//! clipboard shared by all the frames, and an undo history per frame. Every pixel is drawn with
+//! two characters like `Display`, the pixels of the previous frame can be shown under the
+//! current one.
+
+use crate::{BlendMode, Frame};
+
+/// a pixel of an empty frame lit in the previous frame
+const ONION: &str = "░░";
+
+/// the snapshots of a frame before and after the undone edits
+#[derive(Debug, Clone, Default)]
+struct History {
+    undo: Vec<Frame>,
+    redo: Vec<Frame>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Editor {
+    /// the frames, named after their file
+    pub frames: Vec<(String, Frame)>,
+    /// the index of the edited frame
+    pub current: usize,
+    /// the (x, y) position of the cursor
+    pub cursor: (usize, usize),
+    /// the corner of the selection, the opposite one is the cursor
+    pub anchor: Option<(usize, usize)>,
+    pub clipboard: Option<Frame>,
+    /// draw the previous frame under the edited one
+    pub onion: bool,
+    history: Vec<History>,
+    /// the frames as they are in their file
+    saved: Vec<Frame>,
+}
+
+impl Editor {
+    pub fn new(frames: Vec<(String, Frame)>) -> Self {
+        Editor {
+            history: vec![History::default(); frames.len()],
+            saved: frames.iter().map(|(_, frame)| frame.clone()).collect(),
+            frames,
+            current: 0,
+            cursor: (0, 0),
+            anchor: None,
+            clipboard: None,
+            onion: false,
+        }
+    }
+
+    /// the edited frame
+    pub fn frame(&self) -> &Frame {
+        &self.frames[self.current].1
+    }
+
+    /// the (x, y, width, height) of the selection
+    pub fn selection(&self) -> Option<(usize, usize, usize, usize)> {
+        let (ax, ay) = self.anchor?;
+        let (cx, cy) = self.cursor;
+        Some((
+            ax.min(cx),
+            ay.min(cy),
+            ax.max(cx) - ax.min(cx) + 1,
+            ay.max(cy) - ay.min(cy) + 1,
+        ))
+    }
+
+    /// move the cursor, it stays in the frame
+    pub fn move_cursor(&mut self, dx: isize, dy: isize) {
+        let (width, height) = self.frame().dimensions();
+        let clamp = |position: usize, delta: isize, len: usize| {
+            (position as isize + delta).clamp(0, len as isize - 1) as usize
+        };
+        self.cursor = (
+            clamp(self.cursor.0, dx, width),
+            clamp(self.cursor.1, dy, height),
+        );
+    }
+
+    /// edit another frame, the cursor stays at the same position if it's in the frame
+    pub fn go_to(&mut self, index: usize) {
+        self.current = index % self.frames.len();
+        self.anchor = None;
+        self.move_cursor(0, 0);
+    }
+
+    /// start a selection at the cursor, or cancel the current one
+    pub fn toggle_selection(&mut self) {
+        self.anchor = match self.anchor {
+            Some(_) => None,
+            None => Some(self.cursor),
+        };
+    }
+
+    /// change the edited frame, the previous version can be restored with `undo`
+    fn edit(&mut self, edit: impl FnOnce(&mut Frame)) {
+        let frame = &mut self.frames[self.current].1;
+        let before = frame.clone();
+        edit(frame);
+        if *frame != before {
+            let history = &mut self.history[self.current];
+            history.undo.push(before);
+            history.redo.clear();
+        }
+    }
+
+    /// toggle the pixel under the cursor
+    pub fn toggle(&mut self) {
+        let (x, y) = self.cursor;
+        self.edit(|frame| {
+            let on = frame.get(x, y) == Some(true);
+            frame.set(x, y, !on);
+        });
+    }
+
+    /// copy the selection, or the pixel under the cursor, in the clipboard
+    pub fn copy(&mut self) {
+        let (x, y, width, height) =
+            self.selection()
+                .unwrap_or((self.cursor.0, self.cursor.1, 1, 1));
+        self.clipboard = self.frame().crop(x, y, width, height).ok();
+        self.anchor = None;
+    }
+
+    /// copy the selection in the clipboard and turn its pixels off
+    pub fn cut(&mut self) {
+        let selection = self
+            .selection()
+            .unwrap_or((self.cursor.0, self.cursor.1, 1, 1));
+        self.copy();
+        let (x, y, width, height) = selection;
+        self.edit(|frame| {
+            frame.blit(
+                &Frame::empty(width, height),
+                x as isize,
+                y as isize,
+                BlendMode::Copy,
+            )
+        });
+    }
+
+    /// paste the clipboard with its top left corner on the cursor, what's out of the frame is
+    /// lost
+    pub fn paste(&mut self) {
+        if let Some(clipboard) = self.clipboard.clone() {
+            let (x, y) = self.cursor;
+            self.edit(|frame| frame.blit(&clipboard, x as isize, y as isize, BlendMode::Copy));
+        }
+    }
+
+    /// cancel the last edit of the frame, return false when there's nothing to undo
+    pub fn undo(&mut self) -> bool {
+        let history = &mut self.history[self.current];
+        match history.undo.pop() {
+            Some(frame) => {
+                let undone = std::mem::replace(&mut self.frames[self.current].1, frame);
+                history.redo.push(undone);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// redo the last undone edit of the frame, return false when there's nothing to redo
+    pub fn redo(&mut self) -> bool {
+        let history = &mut self.history[self.current];
+        match history.redo.pop() {
+            Some(frame) => {
+                let redone = std::mem::replace(&mut self.frames[self.current].1, frame);
+                history.undo.push(redone);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// the frame at `index` is different from its file
+    pub fn is_modified(&self, index: usize) -> bool {
+        self.frames[index].1 != self.saved[index]
+    }
+
+    /// save the modified frames to their file and return their names
+    pub fn save(&mut self) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let mut saved = Vec::new();
+        for (i, (name, frame)) in self.frames.iter().enumerate() {
+            if self.is_modified(i) {
+                frame
+                    .save_to_file(name)
+                    .map_err(|error| format!("{}: {}", name, error))?;
+                self.saved[i] = frame.clone();
+                saved.push(name.clone());
+            }
+        }
+        Ok(saved)
+    }
+
+    /// draw the edited frame: the cursor is `[]` on a pixel off and `▐▌` on a pixel on, the
+    /// selected pixels are `▓▓` and `··`, and the pixels of the previous frame under the pixels
+    /// off are `░░` with the onion skin
+    pub fn render(&self) -> String {
+        let frame = self.frame();
+        let (width, height) = frame.dimensions();
+        let previous = match self.current {
+            0 => None,
+            current if self.onion => Some(&self.frames[current - 1].1),
+            _ => None,
+        };
+        let selected = |x: usize, y: usize| {
+            self.selection().is_some_and(|(sx, sy, sw, sh)| {
+                (sx..sx + sw).contains(&x) && (sy..sy + sh).contains(&y)
+            })
+        };
+
+        let mut s = String::new();
+        for y in 0..height {
+            for x in 0..width {
+                let on = frame.get(x, y) == Some(true);
+                s.push_str(match (on, (x, y) == self.cursor, selected(x, y)) {
+                    (true, true, _) => "▐▌",
+                    (false, true, _) => "[]",
+                    (true, false, true) => "▓▓",
+                    (false, false, true) => "··",
+                    (true, false, false) => "██",
+                    (false, false, false) => {
+                        match previous.and_then(|previous| previous.get(x, y)) {
+                            Some(true) => ONION,
+                            _ => "  ",
+                        }
+                    }
+                });
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    /// the line under the frame
+    pub fn status(&self) -> String {
+        let (name, _) = &self.frames[self.current];
+        let mut status = format!(
+            "{}/{} {}{}  ({}, {})",
+            self.current + 1,
+            self.frames.len(),
+            name,
+            if self.is_modified(self.current) {
+                " [modified]"
+            } else {
+                ""
+            },
+            self.cursor.0,
+            self.cursor.1
+        );
+        if let Some((_, _, width, height)) = self.selection() {
+            status.push_str(&format!("  selection {} × {}", width, height));
+        }
+        if let Some(clipboard) = &self.clipboard {
+            status.push_str(&format!(
+                "  clipboard {} × {}",
+                clipboard.width(),
+                clipboard.height()
+            ));
+        }
+        if self.onion {
+            status.push_str("  onion");
+        }
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor() -> Editor {
+        let frame = |pixels: &[(usize, usize)]| {
+            let mut frame = Frame::empty(4, 3);
+            for &(x, y) in pixels {
+                frame.set(x, y, true);
+            }
+            frame
+        };
+        Editor::new(vec![
+            ("first".to_string(), frame(&[(0, 0), (1, 0), (1, 1)])),
+            ("second".to_string(), frame(&[(3, 2)])),
+        ])
+    }
+
+    #[test]
+    fn test_toggle_undo_redo() {
+        let mut editor = editor();
+        editor.move_cursor(2, 5);
+        assert_eq!(editor.cursor, (2, 2));
+        editor.toggle();
+        assert_eq!(editor.frame().get(2, 2), Some(true));
+        assert!(editor.is_modified(0));
+
+        assert!(editor.undo());
+        assert_eq!(editor.frame().get(2, 2), Some(false));
+        assert!(!editor.is_modified(0));
+        assert!(!editor.undo());
+        assert!(editor.redo());
+        assert_eq!(editor.frame().get(2, 2), Some(true));
+        assert!(!editor.redo());
+
+        // every frame has its own history
+        editor.go_to(1);
+        assert!(!editor.undo());
+    }
+
+    #[test]
+    fn test_copy_paste_between_frames() {
+        let mut editor = editor();
+        editor.toggle_selection();
+        editor.move_cursor(1, 1);
+        assert_eq!(editor.selection(), Some((0, 0, 2, 2)));
+        editor.copy();
+        assert_eq!(editor.selection(), None);
+
+        editor.go_to(1);
+        assert_eq!(editor.cursor, (1, 1));
+        editor.move_cursor(2, 0);
+        editor.paste();
+        // the right column is out of the frame, and the pixels off are pasted too
+        let mut expected = Frame::empty(4, 3);
+        expected.set(3, 1, true);
+        assert_eq!(editor.frame(), &expected);
+        assert!(editor.undo());
+        assert_eq!(editor.frame().get(3, 1), Some(false));
+        assert_eq!(editor.frame().get(3, 2), Some(true));
+
+        // cut turns the pixels off
+        editor.go_to(0);
+        editor.cursor = (0, 0);
+        editor.toggle_selection();
+        editor.move_cursor(1, 1);
+        editor.cut();
+        assert_eq!(editor.frame(), &Frame::empty(4, 3));
+        assert_eq!(editor.clipboard.as_ref().unwrap().get(0, 0), Some(true));
+    }
+
+    #[test]
+    fn test_render() {
+        let mut editor = editor();
+        editor.move_cursor(1, 0);
+        assert_eq!(editor.render(), "██▐▌    \n  ██    \n        \n");
+        editor.toggle_selection();
+        editor.move_cursor(1, 1);
+        assert_eq!(editor.render(), "██▓▓··  \n  ▓▓[]  \n        \n");
+
+        editor.go_to(1);
+        editor.onion = true;
+        assert_eq!(editor.render(), "░░░░    \n  ░░[]  \n      ██\n");
+        assert!(editor.status().starts_with("2/2 second  (2, 1)"));
+    }
+
+    #[test]
+    fn test_save() {
+        let path = std::env::temp_dir().join(format!("kyria_oled_editor_{}", std::process::id()));
+        let name = path.to_str().unwrap().to_string();
+        let mut editor = Editor::new(vec![(name.clone(), Frame::empty(3, 2))]);
+        assert!(editor.save().unwrap().is_empty());
+
+        editor.toggle();
+        assert_eq!(editor.save().unwrap(), vec![name.clone()]);
+        assert!(!editor.is_modified(0));
+        assert_eq!(&Frame::create_from_file(&name).unwrap(), editor.frame());
+
+        // an empty file can't be opened
+        std::fs::write(&path, "").unwrap();
+        assert!(Frame::create_from_file(&name).is_err());
+        std::fs::write(&path, "\n\n").unwrap();
+        assert!(Frame::create_from_file(&name).is_err());
+
+        // the stray characters are errors, the CRLF line endings are accepted
+        std::fs::write(&path, "#. \n..\n").unwrap();
+        let error = Frame::create_from_file(&name).unwrap_err().to_string();
+        assert!(error.ends_with(":1: unexpected character ' ', a pixel is `.` or `#`"));
+        std::fs::write(&path, "#.\r\n..\r\n").unwrap();
+        let mut expected = Frame::empty(2, 2);
+        expected.set(0, 0, true);
+        assert_eq!(Frame::create_from_file(&name).unwrap(), expected);
+        std::fs::remove_file(&path).unwrap();
+
+        // a failed save keeps the edits
+        let missing = path.join("missing").to_str().unwrap().to_string();
+        let mut editor = Editor::new(vec![(missing.clone(), Frame::empty(3, 2))]);
+        editor.toggle();
+        assert!(editor.save().unwrap_err().to_string().starts_with(&missing));
+        assert!(editor.is_modified(0));
+    }
+}
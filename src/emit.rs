@@ -0,0 +1,453 @@
+//! write byte arrays as source code, or as a binary blob, to any `io::Write`.
+
+use std::io::{self, Write};
+
+/// how the arrays are named and their values written, shared by all the emitters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Style {
+    /// prepended to the name of every array
+    pub prefix: String,
+    /// write the values as `0x2a` instead of `42`
+    pub hex: bool,
+    /// the lines are wrapped before this number of columns
+    pub width: usize,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style {
+            prefix: String::new(),
+            hex: false,
+            width: 80,
+        }
+    }
+}
+
+impl Style {
+    fn name(&self, name: &str) -> String {
+        format!("{}{}", self.prefix, name)
+    }
+
+    fn value(&self, byte: u8) -> String {
+        if self.hex {
+            format!("0x{:02x}", byte)
+        } else {
+            byte.to_string()
+        }
+    }
+
+    /// write the values separated by commas, a line is wrapped when it reaches `width`
+    fn write_values(&self, out: &mut dyn Write, data: &[u8]) -> io::Result<()> {
+        let (last, values) = match data.split_last() {
+            Some(split) => split,
+            None => return Ok(()),
+        };
+        let mut col = 0;
+        for byte in values {
+            let tmp = format!("{}, ", self.value(*byte));
+            col += tmp.len();
+            if col >= self.width {
+                col = tmp.len();
+                writeln!(out)?;
+            }
+            write!(out, "{}", tmp)?;
+        }
+        writeln!(out, "{}", self.value(*last))
+    }
+}
+
+/// write named byte arrays in some format
+pub trait Emitter {
+    /// write the array `name` containing `data`
+    fn array(&mut self, out: &mut dyn Write, name: &str, data: &[u8]) -> io::Result<()>;
+
+    /// make `name` another name of the array `target`, already written, of `len` bytes
+    fn alias(
+        &mut self,
+        out: &mut dyn Write,
+        name: &str,
+        target: &str,
+        len: usize,
+    ) -> io::Result<()>;
+
+    /// write a comment, if the format has comments
+    fn comment(&mut self, _out: &mut dyn Write, _text: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// write what has to be written after the last array
+    fn finish(&mut self, _out: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// C arrays of `uint8_t`, declared like `static const uint8_t PROGMEM NAME[4] = { ... };`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CEmitter {
+    pub is_static: bool,
+    pub is_const: bool,
+    /// written between the type and the name, like `PROGMEM`, nothing when it's empty
+    pub qualifier: String,
+    /// put the arrays in this linker section with `__attribute__((section(...)))`
+    pub section: Option<String>,
+    pub style: Style,
+}
+
+impl Default for CEmitter {
+    /// the arrays stored in the flash of AVR with `PROGMEM`
+    fn default() -> Self {
+        CEmitter {
+            is_static: true,
+            is_const: true,
+            qualifier: "PROGMEM".to_string(),
+            section: None,
+            style: Style::default(),
+        }
+    }
+}
+
+impl Emitter for CEmitter {
+    fn array(&mut self, out: &mut dyn Write, name: &str, data: &[u8]) -> io::Result<()> {
+        let mut declaration = String::new();
+        if self.is_static {
+            declaration.push_str("static ");
+        }
+        if self.is_const {
+            declaration.push_str("const ");
+        }
+        declaration.push_str("uint8_t ");
+        if !self.qualifier.is_empty() {
+            declaration.push_str(&self.qualifier);
+            declaration.push(' ');
+        }
+        declaration.push_str(&format!("{}[{}]", self.style.name(name), data.len()));
+        if let Some(section) = &self.section {
+            declaration.push_str(&format!(" __attribute__((section(\"{}\")))", section));
+        }
+
+        writeln!(out, "{} = {{", declaration)?;
+        self.style.write_values(out, data)?;
+        writeln!(out, "}};")
+    }
+
+    fn alias(&mut self, out: &mut dyn Write, name: &str, target: &str, _: usize) -> io::Result<()> {
+        writeln!(
+            out,
+            "#define {} {}",
+            self.style.name(name),
+            self.style.name(target)
+        )
+    }
+
+    fn comment(&mut self, out: &mut dyn Write, text: &str) -> io::Result<()> {
+        writeln!(out, "// {}", text)
+    }
+}
+
+/// the kind of rust item declaring an array
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RustItem {
+    Const,
+    Static,
+}
+
+/// rust arrays, declared like `const NAME: [u8; 4] = [ ... ];`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RustEmitter {
+    pub item: RustItem,
+    /// declare the arrays as `&[u8]` instead of `[u8; N]`
+    pub slice: bool,
+    pub style: Style,
+}
+
+impl Default for RustEmitter {
+    fn default() -> Self {
+        RustEmitter {
+            item: RustItem::Const,
+            slice: false,
+            style: Style::default(),
+        }
+    }
+}
+
+impl Emitter for RustEmitter {
+    fn array(&mut self, out: &mut dyn Write, name: &str, data: &[u8]) -> io::Result<()> {
+        let item = match self.item {
+            RustItem::Const => "const",
+            RustItem::Static => "static",
+        };
+        let name = self.style.name(name);
+        if self.slice {
+            writeln!(out, "{} {}: &[u8] = &[", item, name)?;
+        } else {
+            writeln!(out, "{} {}: [u8; {}] = [", item, name, data.len())?;
+        }
+        self.style.write_values(out, data)?;
+        writeln!(out, "];")
+    }
+
+    fn alias(
+        &mut self,
+        out: &mut dyn Write,
+        name: &str,
+        target: &str,
+        len: usize,
+    ) -> io::Result<()> {
+        let item = match self.item {
+            RustItem::Const => "const",
+            RustItem::Static => "static",
+        };
+        let (name, target) = (self.style.name(name), self.style.name(target));
+        if self.slice {
+            writeln!(out, "{} {}: &[u8] = {};", item, name, target)
+        } else {
+            writeln!(out, "{} {}: [u8; {}] = {};", item, name, len, target)
+        }
+    }
+
+    fn comment(&mut self, out: &mut dyn Write, text: &str) -> io::Result<()> {
+        writeln!(out, "// {}", text)
+    }
+}
+
+/// MicroPython and CircuitPython `bytes` literals, which stay in flash when the module is
+/// frozen. The values are always escaped in hexadecimal, `hex` is ignored
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PythonEmitter {
+    pub style: Style,
+}
+
+impl Emitter for PythonEmitter {
+    fn array(&mut self, out: &mut dyn Write, name: &str, data: &[u8]) -> io::Result<()> {
+        writeln!(out, "{} = (", self.style.name(name))?;
+        // `    b"` and `"` around 4 columns per byte
+        let per_line = (self.style.width.saturating_sub(7) / 4).max(1);
+        for line in data.chunks(per_line) {
+            let escaped: String = line.iter().map(|byte| format!("\\x{:02x}", byte)).collect();
+            writeln!(out, "    b\"{}\"", escaped)?;
+        }
+        if data.is_empty() {
+            writeln!(out, "    b\"\"")?;
+        }
+        writeln!(out, ")")
+    }
+
+    fn alias(&mut self, out: &mut dyn Write, name: &str, target: &str, _: usize) -> io::Result<()> {
+        writeln!(
+            out,
+            "{} = {}",
+            self.style.name(name),
+            self.style.name(target)
+        )
+    }
+
+    fn comment(&mut self, out: &mut dyn Write, text: &str) -> io::Result<()> {
+        writeln!(out, "# {}", text)
+    }
+}
+
+/// a binary blob written by `finish`: the number of arrays, then the offset from the start of
+/// the blob and the length of every array, all as little endian `u32`, then the arrays one
+/// after the other. An alias has the offset and the length of its target
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BinEmitter {
+    /// the name, offset in `data` and length of every array
+    entries: Vec<(String, usize, usize)>,
+    data: Vec<u8>,
+}
+
+impl BinEmitter {
+    /// the name, offset and length of every array in the blob
+    pub fn index(&self) -> Vec<(String, usize, usize)> {
+        let header = 4 + 8 * self.entries.len();
+        self.entries
+            .iter()
+            .map(|(name, offset, len)| (name.clone(), header + offset, *len))
+            .collect()
+    }
+}
+
+impl Emitter for BinEmitter {
+    fn array(&mut self, _out: &mut dyn Write, name: &str, data: &[u8]) -> io::Result<()> {
+        self.entries
+            .push((name.to_string(), self.data.len(), data.len()));
+        self.data.extend_from_slice(data);
+        Ok(())
+    }
+
+    fn alias(
+        &mut self,
+        _out: &mut dyn Write,
+        name: &str,
+        target: &str,
+        _: usize,
+    ) -> io::Result<()> {
+        let (_, offset, len) = self
+            .entries
+            .iter()
+            .find(|(entry, _, _)| entry == target)
+            .cloned()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "the alias `{}` targets the unknown array `{}`",
+                        name, target
+                    ),
+                )
+            })?;
+        self.entries.push((name.to_string(), offset, len));
+        Ok(())
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(&(self.entries.len() as u32).to_le_bytes())?;
+        for (_, offset, len) in self.index() {
+            out.write_all(&(offset as u32).to_le_bytes())?;
+            out.write_all(&(len as u32).to_le_bytes())?;
+        }
+        out.write_all(&self.data)
+    }
+}
+
+/// write a single array with `emitter` and return it as a string
+pub fn format_array(emitter: &mut dyn Emitter, name: &str, data: &[u8]) -> String {
+    let mut out = Vec::new();
+    emitter
+        .array(&mut out, name, data)
+        .and_then(|_| emitter.finish(&mut out))
+        .expect("writing to a vec can't fail");
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_c_emitter() {
+        let mut emitter = CEmitter::default();
+        assert_eq!(
+            format_array(&mut emitter, "FRAME", &[1, 2, 255]),
+            "static const uint8_t PROGMEM FRAME[3] = {\n1, 2, 255\n};\n"
+        );
+
+        let mut emitter = CEmitter {
+            is_static: false,
+            qualifier: String::new(),
+            section: Some(".oled".to_string()),
+            style: Style {
+                prefix: "BONGO_".to_string(),
+                hex: true,
+                width: 13,
+            },
+            ..CEmitter::default()
+        };
+        assert_eq!(
+            format_array(&mut emitter, "FRAME", &[1, 2, 255, 0]),
+            "const uint8_t BONGO_FRAME[4] __attribute__((section(\".oled\"))) = {\n\
+             0x01, 0x02, \n0xff, 0x00\n};\n"
+        );
+    }
+
+    #[test]
+    fn test_rust_emitter() {
+        let mut emitter = RustEmitter::default();
+        assert_eq!(
+            format_array(&mut emitter, "FRAME", &[1, 2]),
+            "const FRAME: [u8; 2] = [\n1, 2\n];\n"
+        );
+        let mut emitter = RustEmitter {
+            item: RustItem::Static,
+            slice: true,
+            ..RustEmitter::default()
+        };
+        assert_eq!(
+            format_array(&mut emitter, "FRAME", &[1, 2]),
+            "static FRAME: &[u8] = &[\n1, 2\n];\n"
+        );
+    }
+
+    #[test]
+    fn test_python_emitter() {
+        let mut emitter = PythonEmitter {
+            style: Style {
+                width: 15,
+                ..Style::default()
+            },
+        };
+        assert_eq!(
+            format_array(&mut emitter, "FRAME", &[1, 2, 255]),
+            "FRAME = (\n    b\"\\x01\\x02\"\n    b\"\\xff\"\n)\n"
+        );
+    }
+
+    #[test]
+    fn test_bin_emitter() {
+        let mut emitter = BinEmitter::default();
+        let mut out = Vec::new();
+        emitter.array(&mut out, "A", &[1, 2]).unwrap();
+        emitter.array(&mut out, "B", &[3]).unwrap();
+        emitter.alias(&mut out, "C", "A", 2).unwrap();
+        assert!(emitter.alias(&mut out, "D", "E", 2).is_err());
+        assert!(out.is_empty());
+        emitter.finish(&mut out).unwrap();
+
+        assert_eq!(
+            emitter.index(),
+            vec![
+                ("A".to_string(), 28, 2),
+                ("B".to_string(), 30, 1),
+                ("C".to_string(), 28, 2)
+            ]
+        );
+        assert_eq!(
+            out,
+            vec![
+                3, 0, 0, 0, 28, 0, 0, 0, 2, 0, 0, 0, 30, 0, 0, 0, 1, 0, 0, 0, 28, 0, 0, 0, 2, 0, 0,
+                0, 1, 2, 3
+            ]
+        );
+    }
+
+    #[test]
+    fn test_emitted_arrays_parse() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut source = String::new();
+        for hex in &[false, true] {
+            let style = Style {
+                hex: *hex,
+                ..Style::default()
+            };
+            source.push_str(&format_array(
+                &mut CEmitter {
+                    style: style.clone(),
+                    ..CEmitter::default()
+                },
+                if *hex { "C_HEX" } else { "C" },
+                &data,
+            ));
+            source.push_str(&format_array(
+                &mut RustEmitter {
+                    style,
+                    ..RustEmitter::default()
+                },
+                if *hex { "RUST_HEX" } else { "RUST" },
+                &data,
+            ));
+        }
+
+        let mut aliases = Vec::new();
+        CEmitter::default()
+            .alias(&mut aliases, "C_ALIAS", "C", 256)
+            .unwrap();
+        RustEmitter::default()
+            .alias(&mut aliases, "RUST_ALIAS", "RUST", 256)
+            .unwrap();
+        source.push_str(std::str::from_utf8(&aliases).unwrap());
+
+        let arrays = crate::parse::parse_arrays(&source).unwrap();
+        assert_eq!(arrays.len(), 6);
+        assert!(arrays.iter().all(|array| array.data == data));
+    }
+}
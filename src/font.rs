@@ -0,0 +1,483 @@
+use crate::{BlendMode, Frame, OutputMode};
+use std::collections::BTreeMap;
+
+/// a fixed width bitmap font, every glyph is drawn in a cell of `width` × `height` pixels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Font {
+    width: usize,
+    height: usize,
+    glyphs: BTreeMap<char, Frame>,
+}
+
+impl Font {
+    /// create a font from a glcdfont table, like the `font` array of the QMK `glcdfont.c`.
+    ///
+    /// Every glyph is `glyph_width` bytes, one byte per column with the top pixel in the least
+    /// significant bit. The glyphs are in the code page 437 order, the first glyph is the
+    /// character 0 and the glyphs from 128 are the accented letters, the box drawing and the
+    /// greek letters of the IBM PC. QMK uses 6 bytes per glyph while
+    /// the original Adafruit table uses 5. Only the `height` top rows of every byte are kept,
+    /// 8 for the QMK font and 7 for the Adafruit 5×7 font whose bottom row is the gap between
+    /// the lines.
+    pub fn from_glcdfont(
+        data: &[u8],
+        glyph_width: usize,
+        height: usize,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        if glyph_width == 0 || !data.len().is_multiple_of(glyph_width) {
+            return Err(format!(
+                "a glcdfont of {} bytes can't contain glyphs of {} bytes",
+                data.len(),
+                glyph_width
+            )
+            .into());
+        }
+        if height == 0 || height > 8 {
+            return Err(format!("the glyphs of a glcdfont can't be {} pixels high", height).into());
+        }
+        if data.len() / glyph_width > 256 {
+            return Err("a glcdfont can't contain more than 256 glyphs".into());
+        }
+
+        let glyphs = data
+            .chunks(glyph_width)
+            .enumerate()
+            .map(|(code, glyph)| {
+                let glyph = Frame::new(glyph_width, 8, glyph)?.crop(0, 0, glyph_width, height)?;
+                Ok((cp437(code as u8), glyph))
+            })
+            .collect::<Result<_, Box<dyn std::error::Error>>>()?;
+
+        Ok(Self {
+            width: glyph_width,
+            height,
+            glyphs,
+        })
+    }
+
+    /// read the `font` array of a glcdfont C file, see `from_glcdfont`
+    pub fn load_glcdfont(
+        filename: &str,
+        glyph_width: usize,
+        height: usize,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let source = std::fs::read_to_string(filename)?;
+        let font = crate::parse::parse_arrays(&source)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("{}: there is no array in this file", filename))?;
+        Self::from_glcdfont(&font.data, glyph_width, height)
+    }
+
+    /// parse a font in the BDF format.
+    ///
+    /// All the glyphs are placed in cells the size of the `FONTBOUNDINGBOX`, the advance of the
+    /// glyphs is ignored.
+    pub fn from_bdf(source: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut bounding_box = None;
+        let mut glyphs = BTreeMap::new();
+
+        let mut lines = source.lines().enumerate();
+        while let Some((line_nb, line)) = lines.next() {
+            let error = |msg: &str| format!("line {}: {}", line_nb + 1, msg);
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words.as_slice() {
+                ["FONTBOUNDINGBOX", values @ ..] => {
+                    bounding_box = Some(
+                        parse_bbx(values)
+                            .filter(|&(width, height, _, _)| width > 0 && height > 0)
+                            .ok_or_else(|| error("invalid box"))?,
+                    )
+                }
+                ["STARTCHAR", ..] => {
+                    let (width, height, x_offset, y_offset) = bounding_box
+                        .ok_or_else(|| error("glyph defined before FONTBOUNDINGBOX"))?;
+                    let mut code = None;
+                    let mut bbx = None;
+                    let mut cell = Frame::empty(width as usize, height as usize);
+
+                    while let Some((line_nb, line)) = lines.next() {
+                        let error = |msg: &str| format!("line {}: {}", line_nb + 1, msg);
+                        let words: Vec<&str> = line.split_whitespace().collect();
+                        match words.as_slice() {
+                            ["ENCODING", encoding] => {
+                                code = encoding.parse::<u32>().ok().and_then(std::char::from_u32)
+                            }
+                            ["BBX", values @ ..] => {
+                                bbx = Some(parse_bbx(values).ok_or_else(|| error("invalid BBX"))?)
+                            }
+                            ["BITMAP"] => {
+                                let (w, h, x, y) = bbx.ok_or_else(|| error("BITMAP before BBX"))?;
+                                // the position of the glyph in the cell, the baseline is at
+                                // `height + y_offset` from the top of the cell
+                                let left = x - x_offset;
+                                let top = height + y_offset - y - h;
+
+                                for row in 0..h {
+                                    let (_, hex) = lines
+                                        .next()
+                                        .ok_or_else(|| error("the bitmap is too short"))?;
+                                    let bytes = parse_bitmap_row(hex.trim())
+                                        .ok_or_else(|| error("invalid bitmap"))?;
+                                    if w as usize > bytes.len() * 8 {
+                                        return Err(
+                                            error("the bitmap is narrower than the BBX").into()
+                                        );
+                                    }
+                                    for col in 0..w {
+                                        let byte = bytes[col as usize / 8];
+                                        if byte >> (7 - col % 8) & 1 == 1 {
+                                            let (x, y) = (left + col, top + row);
+                                            if x >= 0 && y >= 0 {
+                                                cell.set(x as usize, y as usize, true);
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            ["ENDCHAR"] => break,
+                            _ => (),
+                        }
+                    }
+
+                    // unencoded glyphs have a negative or missing encoding
+                    if let Some(code) = code {
+                        glyphs.insert(code, cell);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let (width, height, _, _) = bounding_box.ok_or("there is no FONTBOUNDINGBOX")?;
+        Ok(Self {
+            width: width as usize,
+            height: height as usize,
+            glyphs,
+        })
+    }
+
+    /// read a font in the BDF format, see `from_bdf`
+    pub fn load_bdf(filename: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let source = std::fs::read_to_string(filename)?;
+        Self::from_bdf(&source).map_err(|e| format!("{}: {}", filename, e).into())
+    }
+
+    /// return the dimensions of a glyph in this order: (width, height)
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// return the glyph of a character
+    pub fn glyph(&self, c: char) -> Option<&Frame> {
+        self.glyphs.get(&c)
+    }
+
+    /// return the size in pixels of a text, in this order: (width, height)
+    pub fn text_dimensions(&self, text: &str) -> (usize, usize) {
+        let width = text
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        (width * self.width, text.lines().count() * self.height)
+    }
+
+    /// draw a text on a frame with its top left corner at (`x`, `y`), the text going outside
+    /// of the frame is clipped.
+    ///
+    /// The text is drawn over the frame, unless `inverse` is set, then every cell is filled
+    /// and the glyphs are drawn in black. The unknown characters are drawn as blank cells.
+    pub fn draw(&self, frame: &mut Frame, x: isize, y: isize, text: &str, inverse: bool) {
+        let blank = Frame::empty(self.width, self.height);
+
+        for (line_nb, line) in text.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                let x = x + (col * self.width) as isize;
+                let y = y + (line_nb * self.height) as isize;
+                let glyph = self.glyphs.get(&c).unwrap_or(&blank);
+
+                if inverse {
+                    let mut glyph = glyph.clone();
+                    glyph.invert();
+                    frame.blit(&glyph, x, y, BlendMode::Copy);
+                } else {
+                    frame.blit(glyph, x, y, BlendMode::Or);
+                }
+            }
+        }
+    }
+
+    /// generate the glyph atlas of the specified characters.
+    ///
+    /// Every glyph is stored in the OLED format, each glyph takes
+    /// `width * ceil(height / 8)` bytes.
+    pub fn atlas(&self, chars: &str) -> Vec<u8> {
+        let blank = Frame::empty(self.width, self.height.div_ceil(8) * 8);
+
+        chars
+            .chars()
+            .flat_map(|c| {
+                let mut cell = blank.clone();
+                if let Some(glyph) = self.glyphs.get(&c) {
+                    cell.blit(glyph, 0, 0, BlendMode::Copy);
+                }
+                cell.output_with(OutputMode::Full).data
+            })
+            .collect()
+    }
+
+    /// print the glyph atlas of the specified characters as a C array, see `atlas`.
+    /// There must be at least one character, C has no empty array
+    pub fn print_atlas_as_c_array(
+        &self,
+        varname: &str,
+        chars: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if chars.is_empty() {
+            return Err(format!("the atlas `{}` has no character", varname).into());
+        }
+        println!(
+            "// {} × {} glyphs, {} bytes each: {:?}",
+            self.width,
+            self.height,
+            self.width * (self.height.div_ceil(8)),
+            chars
+        );
+        crate::print_slice_as_c_array(varname, &self.atlas(chars));
+        Ok(())
+    }
+}
+
+/// the characters 128 to 255 of the code page 437
+const CP437_HIGH: &str = "ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜ¢£¥₧ƒáíóúñÑªº¿⌐¬½¼¡«»\
+                          ░▒▓│┤╡╢╖╕╣║╗╝╜╛┐└┴┬├─┼╞╟╚╔╩╦╠═╬╧╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀\
+                          αßΓπΣσµτΦΘΩδ∞φε∩≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{a0}";
+
+/// the character of a code of the code page 437, the codes below 128 are ASCII
+fn cp437(code: u8) -> char {
+    if code < 128 {
+        code as char
+    } else {
+        CP437_HIGH.chars().nth(code as usize - 128).unwrap()
+    }
+}
+
+/// parse the four values of a `BBX` or a `FONTBOUNDINGBOX`
+fn parse_bbx(values: &[&str]) -> Option<(isize, isize, isize, isize)> {
+    match values {
+        [w, h, x, y] => Some((
+            w.parse().ok()?,
+            h.parse().ok()?,
+            x.parse().ok()?,
+            y.parse().ok()?,
+        )),
+        _ => None,
+    }
+}
+
+/// parse a row of a `BITMAP`, the hexadecimal bytes of the row padded to a whole byte
+fn parse_bitmap_row(hex: &str) -> Option<Vec<u8>> {
+    if !hex.is_ascii() || !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the glyphs of `0` and `1` from the QMK glcdfont
+    const DIGITS: [u8; 12] = [
+        0x3E, 0x51, 0x49, 0x45, 0x3E, 0x00, 0x00, 0x42, 0x7F, 0x40, 0x00, 0x00,
+    ];
+
+    fn digits() -> Font {
+        let mut data = vec![0; 6 * 48];
+        data.extend_from_slice(&DIGITS);
+        Font::from_glcdfont(&data, 6, 8).unwrap()
+    }
+
+    const BDF: &str = "STARTFONT 2.1
+FONT -test-
+FONTBOUNDINGBOX 4 6 0 -1
+CHARS 2
+STARTCHAR A
+ENCODING 65
+BBX 3 5 0 0
+BITMAP
+40
+A0
+E0
+A0
+A0
+ENDCHAR
+STARTCHAR g
+ENCODING 103
+BBX 3 4 1 -1
+BITMAP
+60
+A0
+60
+C0
+ENDCHAR
+ENDFONT
+";
+
+    fn render(font: &Font, text: &str, inverse: bool) -> String {
+        let (width, height) = font.text_dimensions(text);
+        let mut frame = Frame::empty(width, height);
+        font.draw(&mut frame, 0, 0, text, inverse);
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| if frame.get(x, y).unwrap() { '#' } else { '.' })
+                    .collect::<String>()
+                    + "\n"
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_glcdfont() {
+        let font = digits();
+        assert_eq!(font.dimensions(), (6, 8));
+        assert_eq!(
+            render(&font, "01", false),
+            ".###....#...
+#...#..##...
+#..##...#...
+#.#.#...#...
+##..#...#...
+#...#...#...
+.###...###..
+............
+"
+        );
+        assert!(Font::from_glcdfont(&DIGITS, 5, 8).is_err());
+
+        // the table is in the code page 437 order, not Latin-1
+        assert_eq!(CP437_HIGH.chars().count(), 128);
+        let mut data = vec![0; 256];
+        data[0x82] = 0xff;
+        let font = Font::from_glcdfont(&data, 1, 8).unwrap();
+        assert_eq!(font.atlas("é"), [0xff]);
+        assert_eq!(font.atlas("\u{82}"), [0]);
+        assert_eq!(font.atlas("░"), [0]);
+        assert!(Font::from_glcdfont(&DIGITS, 6, 9).is_err());
+    }
+
+    #[test]
+    fn test_glcdfont_5x7() {
+        // the Adafruit font without the empty columns of QMK, and without its 8th row
+        let mut data = vec![0; 5 * 48];
+        data.extend_from_slice(&DIGITS[..5]);
+        data.extend_from_slice(&DIGITS[6..11]);
+        let font = Font::from_glcdfont(&data, 5, 7).unwrap();
+        assert_eq!(font.dimensions(), (5, 7));
+        assert_eq!(font.text_dimensions("0\n1"), (5, 14));
+        assert_eq!(
+            render(&font, "0\n1", false),
+            ".###.
+#...#
+#..##
+#.#.#
+##..#
+#...#
+.###.
+..#..
+.##..
+..#..
+..#..
+..#..
+..#..
+.###.
+"
+        );
+        // the atlas still uses full pages
+        assert_eq!(font.atlas("0"), DIGITS[..5]);
+    }
+
+    #[test]
+    fn test_bdf() {
+        let font = Font::from_bdf(BDF).unwrap();
+        assert_eq!(font.dimensions(), (4, 6));
+        assert_eq!(
+            render(&font, "Ag\nx", false),
+            ".#......
+#.#.....
+###...##
+#.#..#.#
+#.#...##
+.....##.
+........
+........
+........
+........
+........
+........
+"
+        );
+    }
+
+    #[test]
+    fn test_bdf_rows() {
+        // a 72 pixels wide glyph doesn't fit in a u64
+        let wide = "FONTBOUNDINGBOX 72 1 0 0
+STARTCHAR wide
+ENCODING 45
+BBX 72 1 0 0
+BITMAP
+800000000000000001
+ENDCHAR
+";
+        let font = Font::from_bdf(wide).unwrap();
+        let (width, height) = font.text_dimensions("-");
+        let mut frame = Frame::empty(width, height);
+        font.draw(&mut frame, 0, 0, "-", false);
+        assert_eq!(frame.get(0, 0), Some(true));
+        assert_eq!(frame.get(1, 0), Some(false));
+        assert_eq!(frame.get(71, 0), Some(true));
+
+        // the BBX is wider than the bitmap
+        assert!(Font::from_bdf(&wide.replace("BBX 72", "BBX 80")).is_err());
+        assert!(Font::from_bdf(&wide.replace("800000000000000001", "8")).is_err());
+
+        // the cells can't be empty
+        assert!(Font::from_bdf(&wide.replace("BOUNDINGBOX 72 1", "BOUNDINGBOX -72 1")).is_err());
+        assert!(Font::from_bdf(&wide.replace("BOUNDINGBOX 72 1", "BOUNDINGBOX 72 0")).is_err());
+    }
+
+    #[test]
+    fn test_inverse_and_clipping() {
+        let font = Font::from_bdf(BDF).unwrap();
+        let mut frame = Frame::empty(6, 3);
+        font.draw(&mut frame, -1, -2, "AA", true);
+        let rendered: String = (0..3)
+            .map(|y| {
+                (0..6)
+                    .map(|x| if frame.get(x, y).unwrap() { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(rendered, "..#... #.#.#. #.#.#.");
+    }
+
+    #[test]
+    fn test_atlas() {
+        let font = digits();
+        assert_eq!(font.atlas("10"), [&DIGITS[6..], &DIGITS[..6]].concat());
+        // an unknown character is blank
+        assert_eq!(font.atlas("\u{1F408}"), vec![0; 6]);
+
+        // a 6 pixels high font still uses a full page
+        let font = Font::from_bdf(BDF).unwrap();
+        assert_eq!(font.atlas("A"), vec![0x1e, 0x05, 0x1e, 0]);
+        assert!(font.print_atlas_as_c_array("EMPTY", "").is_err());
+    }
+}
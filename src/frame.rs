@@ -1,7 +1,139 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Frame {
     frame: Vec<Vec<u8>>,
 }
 
+/// how the pixels of a frame are combined with the pixels it's blitted on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    /// replace the destination pixels
+    Copy,
+    Or,
+    And,
+    Xor,
+}
+
+/// a clockwise rotation.
+///
+/// It's also used to represent the QMK `oled_rotation_t`, `OLED_ROTATION_90` displays the frame
+/// buffer rotated by 90° clockwise on the panel. With `OLED_ROTATION_90` and `OLED_ROTATION_270`
+/// the buffer is in portrait, a 128 × 32 panel has a 32 × 128 buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl Rotation {
+    /// return the rotation cancelling this one
+    pub fn inverse(self) -> Self {
+        match self {
+            Rotation::Deg0 => Rotation::Deg0,
+            Rotation::Deg90 => Rotation::Deg270,
+            Rotation::Deg180 => Rotation::Deg180,
+            Rotation::Deg270 => Rotation::Deg90,
+        }
+    }
+}
+
+impl std::str::FromStr for Rotation {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" => Ok(Rotation::Deg0),
+            "90" => Ok(Rotation::Deg90),
+            "180" => Ok(Rotation::Deg180),
+            "270" => Ok(Rotation::Deg270),
+            s => Err(format!(
+                "invalid rotation `{}`, expected `0`, `90`, `180` or `270`",
+                s
+            )
+            .into()),
+        }
+    }
+}
+
+/// what happens to the pixels moved out of a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    /// the pixels reappear on the other side
+    Wrap,
+    /// the pixels are lost and replaced by black pixels on the other side
+    Clip,
+}
+
+/// how `Frame::render` draws the pixels in a terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderStyle {
+    /// `██` for every pixel, like `Display`
+    Blocks,
+    /// the half blocks `▀▄█`, a character is 1 × 2 pixels
+    HalfBlocks,
+    /// the braille patterns, a character is 2 × 4 pixels
+    Braille,
+    /// two spaces on a white or black ANSI background for every pixel, it looks like the panel
+    /// whatever the colours of the terminal
+    Inverse,
+}
+
+impl std::str::FromStr for RenderStyle {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blocks" => Ok(RenderStyle::Blocks),
+            "half" => Ok(RenderStyle::HalfBlocks),
+            "braille" => Ok(RenderStyle::Braille),
+            "inverse" => Ok(RenderStyle::Inverse),
+            s => Err(format!(
+                "unknown render style `{}`, expected `blocks`, `half`, `braille` or `inverse`",
+                s
+            )
+            .into()),
+        }
+    }
+}
+
+/// which bytes of the frame buffer are output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputMode {
+    /// every byte of the frame buffer
+    Full,
+    /// the trailing zeros are dropped, the length must be recorded to restore the frame
+    Trimmed,
+    /// only the pages between the first and the last page containing something are kept, the
+    /// range of pages must be recorded to restore the frame
+    Pages,
+}
+
+/// a part of a frame buffer, see `Frame::output_with`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Output {
+    /// the position of the first byte of `data` in the frame buffer
+    pub offset: usize,
+    pub data: Vec<u8>,
+    /// the length of the complete frame buffer
+    pub buffer_len: usize,
+}
+
+impl Output {
+    /// the range of pages covered by the data of a frame of width `width`, the last page is
+    /// excluded
+    pub fn pages(&self, width: usize) -> std::ops::Range<usize> {
+        self.offset / width..(self.offset + self.data.len()).div_ceil(width)
+    }
+
+    /// restore the complete frame buffer, the missing bytes are zeros
+    pub fn to_buffer(&self) -> Vec<u8> {
+        let mut buffer = vec![0; self.buffer_len];
+        buffer[self.offset..self.offset + self.data.len()].copy_from_slice(&self.data);
+        buffer
+    }
+}
+
 impl Frame {
     /// create a frame from a width, height and an array of bytes
     /// I used this method to extract the bongo cat animation initially from the C array
@@ -11,9 +143,11 @@ impl Frame {
         height: usize,
         frame: &[u8],
     ) -> Result<Self, Box<dyn std::error::Error>> {
-        if false && frame.len() * 8 != width * height {
+        Self::check_dimensions(width, height)?;
+        // a shorter frame is accepted, like the trimmed output, the missing pixels are off
+        if frame.len() * 8 > width * height {
             return Err(format!(
-                "frame length ({} pixels) not matching with the width and height ({} × {} = {} pixels).",
+                "frame length ({} pixels) bigger than the width and height ({} × {} = {} pixels).",
                 frame.len() * 8,
                 width,
                 height,
@@ -26,7 +160,7 @@ impl Frame {
         for (height, line) in frame.chunks(width).enumerate() {
             for (width, b) in line.iter().enumerate() {
                 let height = height * 8;
-                internal[height][width] = (b >> 0) & 1;
+                internal[height][width] = b & 1;
                 internal[height + 1][width] = (b >> 1) & 1;
                 internal[height + 2][width] = (b >> 2) & 1;
                 internal[height + 3][width] = (b >> 3) & 1;
@@ -40,6 +174,19 @@ impl Frame {
         Ok(Self { frame: internal })
     }
 
+    /// return an error if `new` can't build a frame of these dimensions, every byte is a column
+    /// of 8 pixels so the height must be a multiple of 8. The frames of `empty` only need to
+    /// have a pixel, they must pass this check to be output
+    pub fn check_dimensions(width: usize, height: usize) -> Result<(), Box<dyn std::error::Error>> {
+        if width == 0 || height == 0 {
+            return Err(format!("a frame can't be empty ({} × {})", width, height).into());
+        }
+        if !height.is_multiple_of(8) {
+            return Err(format!("the height ({}) must be a multiple of 8", height).into());
+        }
+        Ok(())
+    }
+
     /// return the height of the frame
     pub fn height(&self) -> usize {
         self.frame.len()
@@ -57,28 +204,51 @@ impl Frame {
 
     /// create a frame from a file, see the examples in the bongo_cat directory
     pub fn create_from_file(filename: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let file = std::fs::read(filename)?;
-        let frame: Vec<Vec<u8>> = file[..file.len() - 1]
-            .split(|&b| b == b'\n')
-            .map(|line| {
-                line.iter()
-                    .map(|b| match b {
-                        b'.' => 0,
-                        b'#' => 1,
-                        _ => panic!("lala"),
+        let file = std::fs::read_to_string(filename)?;
+        if file.is_empty() {
+            return Err(format!("the frame file `{}` is empty", filename).into());
+        }
+        // `lines` also accepts the `\r\n` line endings
+        let frame: Vec<Vec<u8>> = file
+            .lines()
+            .enumerate()
+            .map(|(line_nb, line)| {
+                line.chars()
+                    .map(|c| match c {
+                        '.' => Ok(0),
+                        '#' => Ok(1),
+                        c => Err(format!(
+                            "{}:{}: unexpected character {:?}, a pixel is `.` or `#`",
+                            filename,
+                            line_nb + 1,
+                            c
+                        )),
                     })
-                    .collect::<Vec<u8>>()
+                    .collect::<Result<Vec<u8>, _>>()
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         // just ensure every line is the same width
         if frame.windows(2).any(|v| v[0].len() != v[1].len()) {
-            return Err("All the frame needs to be the same width".into());
+            Err("All the frame needs to be the same width".into())
+        } else if frame[0].is_empty() {
+            Err(format!("the frame in `{}` has no pixel", filename).into())
         } else {
             Ok(Self { frame })
         }
     }
 
+    /// save a frame to a file, in the same format as the one read by `create_from_file`
+    pub fn save_to_file(&self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut file = Vec::with_capacity((self.width() + 1) * self.height());
+        for line in self.frame.iter() {
+            file.extend(line.iter().map(|&b| if b == 0 { b'.' } else { b'#' }));
+            file.push(b'\n');
+        }
+        std::fs::write(filename, file)?;
+        Ok(())
+    }
+
     /// Generate the average of all the frame, probably useless actually
     pub fn create_from_multiple_frame(frames: &[Self]) -> Result<Self, Box<dyn std::error::Error>> {
         if frames
@@ -92,26 +262,176 @@ impl Frame {
 
         let mut v = vec![vec![0; width]; height];
         for frame in frames {
-            for y in 0..height {
-                for x in 0..width {
-                    v[y][x] += frame.frame[y][x];
+            for (line, frame_line) in v.iter_mut().zip(&frame.frame) {
+                for (pixel, frame_pixel) in line.iter_mut().zip(frame_line) {
+                    *pixel += frame_pixel;
                 }
             }
         }
 
-        for y in 0..height {
-            for x in 0..width {
-                if v[y][x] >= frames.len() as u8 / 2 {
-                    v[y][x] = 1;
-                } else {
-                    v[y][x] = 0;
-                }
+        for pixel in v.iter_mut().flatten() {
+            if *pixel >= frames.len() as u8 / 2 {
+                *pixel = 1;
+            } else {
+                *pixel = 0;
             }
         }
 
         Ok(Self { frame: v })
     }
 
+    /// create a black frame.
+    ///
+    /// Panics if `width` or `height` is 0, a frame has at least a pixel. Unlike `new` the height
+    /// doesn't have to be a multiple of 8, see `check_dimensions` before outputting the frame
+    pub fn empty(width: usize, height: usize) -> Self {
+        assert!(
+            width > 0 && height > 0,
+            "a frame can't be empty ({} × {})",
+            width,
+            height
+        );
+        Self {
+            frame: vec![vec![0; width]; height],
+        }
+    }
+
+    /// return the pixel at the specified position, or `None` if it's outside of the frame
+    pub fn get(&self, x: usize, y: usize) -> Option<bool> {
+        self.frame.get(y)?.get(x).map(|&pixel| pixel == 1)
+    }
+
+    /// set the pixel at the specified position, the pixels outside of the frame are ignored
+    pub fn set(&mut self, x: usize, y: usize, on: bool) {
+        if let Some(pixel) = self.frame.get_mut(y).and_then(|line| line.get_mut(x)) {
+            *pixel = on as u8;
+        }
+    }
+
+    /// set all the pixels of the frame
+    pub fn fill(&mut self, on: bool) {
+        for pixel in self.frame.iter_mut().flatten() {
+            *pixel = on as u8;
+        }
+    }
+
+    /// turn on all the pixels that are off and the other way around
+    pub fn invert(&mut self) {
+        for pixel in self.frame.iter_mut().flatten() {
+            *pixel ^= 1;
+        }
+    }
+
+    /// mirror the frame, the left becomes the right
+    pub fn flip_horizontal(&mut self) {
+        for line in self.frame.iter_mut() {
+            line.reverse();
+        }
+    }
+
+    /// mirror the frame, the top becomes the bottom
+    pub fn flip_vertical(&mut self) {
+        self.frame.reverse();
+    }
+
+    /// move all the pixels of the frame by `dx` to the right and `dy` to the bottom
+    pub fn translate(&mut self, dx: isize, dy: isize, edge: Edge) {
+        let (width, height) = (self.width() as isize, self.height() as isize);
+        let mut frame = vec![vec![0; width as usize]; height as usize];
+
+        for (y, line) in self.frame.iter().enumerate() {
+            for (x, &pixel) in line.iter().enumerate() {
+                let (x, y) = (x as isize + dx, y as isize + dy);
+                let (x, y) = match edge {
+                    Edge::Wrap => (x.rem_euclid(width), y.rem_euclid(height)),
+                    Edge::Clip if x < 0 || x >= width || y < 0 || y >= height => continue,
+                    Edge::Clip => (x, y),
+                };
+                frame[y as usize][x as usize] = pixel;
+            }
+        }
+
+        self.frame = frame;
+    }
+
+    /// extract a part of the frame
+    pub fn crop(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        if x + width > self.width() || y + height > self.height() {
+            return Err(format!(
+                "can't crop {} × {} pixels at ({}, {}) in a {} × {} frame",
+                width,
+                height,
+                x,
+                y,
+                self.width(),
+                self.height()
+            )
+            .into());
+        }
+
+        Ok(Self {
+            frame: self.frame[y..y + height]
+                .iter()
+                .map(|line| line[x..x + width].to_vec())
+                .collect(),
+        })
+    }
+
+    /// draw `other` on this frame with its top left corner at (`x`, `y`).
+    /// The parts of `other` going outside of the frame are ignored
+    pub fn blit(&mut self, other: &Frame, x: isize, y: isize, mode: BlendMode) {
+        for (oy, line) in other.frame.iter().enumerate() {
+            for (ox, &src) in line.iter().enumerate() {
+                let (x, y) = (x + ox as isize, y + oy as isize);
+                if x < 0 || y < 0 {
+                    continue;
+                }
+                if let Some(dst) = self
+                    .frame
+                    .get_mut(y as usize)
+                    .and_then(|line| line.get_mut(x as usize))
+                {
+                    *dst = match mode {
+                        BlendMode::Copy => src,
+                        BlendMode::Or => *dst | src,
+                        BlendMode::And => *dst & src,
+                        BlendMode::Xor => *dst ^ src,
+                    };
+                }
+            }
+        }
+    }
+
+    /// return the frame rotated clockwise
+    pub fn rotate(&self, rotation: Rotation) -> Self {
+        let (width, height) = self.dimensions();
+        let (new_width, new_height) = match rotation {
+            Rotation::Deg0 | Rotation::Deg180 => (width, height),
+            Rotation::Deg90 | Rotation::Deg270 => (height, width),
+        };
+
+        let frame = (0..new_height)
+            .map(|y| {
+                (0..new_width)
+                    .map(|x| match rotation {
+                        Rotation::Deg0 => self.frame[y][x],
+                        Rotation::Deg90 => self.frame[height - 1 - x][y],
+                        Rotation::Deg180 => self.frame[height - 1 - y][width - 1 - x],
+                        Rotation::Deg270 => self.frame[x][width - 1 - y],
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self { frame }
+    }
+
     /// display a frame on the screen
     pub fn print(&self) {
         for line in self.frame.iter() {
@@ -126,15 +446,77 @@ impl Frame {
         }
     }
 
+    /// draw a frame for a terminal, every line ends with a newline. The pixels outside of the
+    /// frame are off when the last characters aren't full
+    pub fn render(&self, style: RenderStyle) -> String {
+        let (width, height) = self.dimensions();
+        let on = |x: usize, y: usize| self.get(x, y) == Some(true);
+        let mut s = String::new();
+        match style {
+            RenderStyle::Blocks => s = self.to_string(),
+            RenderStyle::HalfBlocks => {
+                for y in (0..height).step_by(2) {
+                    for x in 0..width {
+                        s.push(match (on(x, y), on(x, y + 1)) {
+                            (false, false) => ' ',
+                            (true, false) => '▀',
+                            (false, true) => '▄',
+                            (true, true) => '█',
+                        });
+                    }
+                    s.push('\n');
+                }
+            }
+            RenderStyle::Braille => {
+                // the bit of the dot of every pixel of a 2 × 4 cell, by column
+                const DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+                for y in (0..height).step_by(4) {
+                    for x in (0..width).step_by(2) {
+                        let mut dots = 0;
+                        for (dx, column) in DOTS.iter().enumerate() {
+                            for (dy, dot) in column.iter().enumerate() {
+                                if on(x + dx, y + dy) {
+                                    dots |= dot;
+                                }
+                            }
+                        }
+                        s.push(std::char::from_u32(0x2800 + dots).expect("a braille pattern"));
+                    }
+                    s.push('\n');
+                }
+            }
+            RenderStyle::Inverse => {
+                for y in 0..height {
+                    // the background only changes between the runs of pixels
+                    let mut previous = None;
+                    for x in 0..width {
+                        if previous != Some(on(x, y)) {
+                            s.push_str(if on(x, y) { "\x1b[107m" } else { "\x1b[40m" });
+                            previous = Some(on(x, y));
+                        }
+                        s.push_str("  ");
+                    }
+                    s.push_str("\x1b[0m\n");
+                }
+            }
+        }
+        s
+    }
+
     /// output a frame as an array of bytes, this format can be provided to an OLED screen to be
-    /// displayed
+    /// displayed. The trailing zeros are dropped, see `output_with` to get every byte
     pub fn output(&self) -> Vec<u8> {
+        self.output_with(OutputMode::Trimmed).data
+    }
+
+    /// output a frame as an array of bytes with the specified mode
+    pub fn output_with(&self, mode: OutputMode) -> Output {
         let width = self.frame[0].len();
         let height = self.frame.len();
 
-        let mut res = (0..height)
+        let buffer = (0..height)
             .step_by(8)
-            .map(move |height| {
+            .flat_map(move |height| {
                 (0..width).map(move |width| {
                     self.frame[height][width]
                         | (self.frame[height + 1][width] << 1)
@@ -146,18 +528,55 @@ impl Frame {
                         | (self.frame[height + 7][width] << 7)
                 })
             })
-            .flatten()
             .collect::<Vec<u8>>();
+        let buffer_len = buffer.len();
 
-        // we remove all the trailings zeros
-        if let Some(i) = res.iter().rposition(|x| *x != 0) {
-            let new_len = i + 1;
-            res.truncate(new_len);
+        let first = buffer.iter().position(|x| *x != 0);
+        let last = buffer.iter().rposition(|x| *x != 0);
+        let (start, end) = match (mode, first, last) {
+            (OutputMode::Full, _, _) => (0, buffer_len),
+            (_, None, _) | (_, _, None) => (0, 0),
+            (OutputMode::Trimmed, _, Some(last)) => (0, last + 1),
+            // we keep complete pages
+            (OutputMode::Pages, Some(first), Some(last)) => (
+                first / width * width,
+                ((last / width + 1) * width).min(buffer_len),
+            ),
+        };
+
+        Output {
+            offset: start,
+            data: buffer[start..end].to_vec(),
+            buffer_len,
         }
-        res
     }
 
-    /// output the result of a compressed frame
+    /// output the frame for a QMK keyboard configured with the specified `oled_rotation_t`.
+    /// The frame is drawn as it must appear on the panel in its native orientation, the bytes
+    /// returned are the whole frame buffer and can be given as is to `oled_write_raw`, the
+    /// trailing zeros are kept to overwrite the previous frame
+    pub fn output_rotated(&self, rotation: Rotation) -> Vec<u8> {
+        self.rotate(rotation.inverse())
+            .output_with(OutputMode::Full)
+            .data
+    }
+
+    /// return the diff between the complete frame buffers of this frame, used as the base, and
+    /// `other`. The two frames must have the same dimensions
+    pub fn diff(&self, other: &Frame) -> Result<Vec<u8>, crate::DiffError> {
+        if self.dimensions() != other.dimensions() {
+            return Err(crate::DiffError::DimensionMismatch {
+                base: self.dimensions(),
+                other: other.dimensions(),
+            });
+        }
+        crate::try_diff(
+            &self.output_with(OutputMode::Full).data,
+            &other.output_with(OutputMode::Full).data,
+        )
+    }
+
+    /// output the result of a compressed frame, the trailing zeros are not compressed
     pub fn compress(&self) -> Vec<u8> {
         crate::compress(&self.output())
     }
@@ -212,9 +631,198 @@ mod tests {
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7, 8, 8, 23, 0, 15, 1, 2, 1, 15, 0, 15, 2, 5, 8,
     ];
 
+    /// create a frame from a drawing using the same format as the frame files
+    fn frame(drawing: &str) -> Frame {
+        Frame {
+            frame: drawing
+                .split_whitespace()
+                .map(|line| line.bytes().map(|b| (b == b'#') as u8).collect())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut f = Frame::empty(3, 2);
+        f.set(1, 1, true);
+        f.set(3, 0, true);
+        assert_eq!(f, frame("... .#."));
+        assert_eq!(f.get(1, 1), Some(true));
+        assert_eq!(f.get(0, 1), Some(false));
+        assert_eq!(f.get(0, 2), None);
+    }
+
+    #[test]
+    fn test_fill_invert() {
+        let mut f = frame("#.. .#.");
+        f.invert();
+        assert_eq!(f, frame(".## #.#"));
+        f.fill(false);
+        assert_eq!(f, Frame::empty(3, 2));
+    }
+
+    #[test]
+    fn test_flip() {
+        let mut f = frame("##. #..");
+        f.flip_horizontal();
+        assert_eq!(f, frame(".## ..#"));
+        f.flip_vertical();
+        assert_eq!(f, frame("..# .##"));
+    }
+
+    #[test]
+    fn test_translate() {
+        let mut f = frame("##. #..");
+        f.translate(2, 1, Edge::Wrap);
+        assert_eq!(f, frame("..# #.#"));
+        f.translate(-1, 0, Edge::Clip);
+        assert_eq!(f, frame(".#. .#."));
+        f.translate(0, 5, Edge::Clip);
+        assert_eq!(f, Frame::empty(3, 2));
+    }
+
+    #[test]
+    fn test_crop() {
+        let f = frame("##.. #..# ..##");
+        assert_eq!(f.crop(1, 1, 3, 2).unwrap(), frame("..# .##"));
+        assert!(f.crop(2, 0, 3, 1).is_err());
+    }
+
+    #[test]
+    fn test_blit() {
+        let base = frame("##.. ##.. ....");
+        let other = frame("#. .#");
+        let blit = |x, y, mode| {
+            let mut f = base.clone();
+            f.blit(&other, x, y, mode);
+            f
+        };
+        assert_eq!(blit(1, 0, BlendMode::Copy), frame("##.. #.#. ...."));
+        assert_eq!(blit(1, 0, BlendMode::Or), frame("##.. ###. ...."));
+        assert_eq!(blit(1, 0, BlendMode::And), frame("##.. #... ...."));
+        assert_eq!(blit(1, 0, BlendMode::Xor), frame("#... ###. ...."));
+        assert_eq!(blit(2, 2, BlendMode::Or), frame("##.. ##.. ..#."));
+        assert_eq!(blit(-1, -1, BlendMode::Xor), frame(".#.. ##.. ...."));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let f = frame("##. ..#");
+        assert_eq!(f.rotate(Rotation::Deg0), f);
+        assert_eq!(f.rotate(Rotation::Deg90), frame(".# .# #."));
+        assert_eq!(f.rotate(Rotation::Deg180), frame("#.. .##"));
+        assert_eq!(f.rotate(Rotation::Deg270), frame(".# #. #."));
+        for rotation in &[Rotation::Deg90, Rotation::Deg180, Rotation::Deg270] {
+            assert_eq!(f.rotate(*rotation).rotate(rotation.inverse()), f);
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let f = frame("#.# .## #..");
+        assert_eq!(f.render(RenderStyle::Blocks), f.to_string());
+        assert_eq!(f.render(RenderStyle::HalfBlocks), "▀▄█\n▀  \n");
+        // the dots 1, 3 and 5 in the first cell, 1 and 2 in the second
+        assert_eq!(f.render(RenderStyle::Braille), "\u{2815}\u{2803}\n");
+        assert_eq!(
+            frame(".# ..").render(RenderStyle::Inverse),
+            "\x1b[40m  \x1b[107m  \x1b[0m\n\x1b[40m    \x1b[0m\n"
+        );
+        assert!("sixel".parse::<RenderStyle>().is_err());
+    }
+
+    #[test]
+    fn test_output_rotated() {
+        let f = Frame::new(128, 40, &FRAME).unwrap();
+        assert_eq!(
+            f.output_rotated(Rotation::Deg0),
+            f.output_with(OutputMode::Full).data
+        );
+
+        // a 128 × 40 frame becomes a 40 × 128 buffer, written whole
+        let portrait = f.output_rotated(Rotation::Deg90);
+        assert_eq!(portrait.len(), 40 * 128 / 8);
+        assert_eq!(
+            Frame::new(40, 128, &portrait)
+                .unwrap()
+                .rotate(Rotation::Deg90),
+            f
+        );
+        assert_ne!(portrait, f.output_rotated(Rotation::Deg270));
+    }
+
     #[test]
     fn test_output() {
         let frame = Frame::new(128, 40, &FRAME).unwrap();
         assert_eq!(&frame.output(), &FRAME);
     }
+
+    #[test]
+    fn test_new_length() {
+        // the missing pages are off, but the data can't be bigger than the frame
+        let frame = Frame::new(2, 16, &[1, 0]).unwrap();
+        assert_eq!(frame.output_with(OutputMode::Full).data, [1, 0, 0, 0]);
+        assert!(Frame::new(2, 8, &[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "a frame can't be empty (0 × 8)")]
+    fn test_empty_without_pixel() {
+        Frame::empty(0, 8);
+    }
+
+    #[test]
+    fn test_new_dimensions() {
+        assert!(Frame::new(0, 8, &[]).is_err());
+        assert!(Frame::new(2, 0, &[]).is_err());
+        // the last byte would fill rows 8 to 15 of a 12 pixels high frame
+        assert!(Frame::new(1, 12, &[0, 0xff]).is_err());
+    }
+
+    #[test]
+    fn test_output_modes() {
+        // the second and fourth pages are empty
+        let mut f = Frame::empty(2, 32);
+        f.set(1, 9, true);
+        f.set(0, 20, true);
+
+        let full = f.output_with(OutputMode::Full);
+        assert_eq!(full.data, [0, 0, 0, 2, 16, 0, 0, 0]);
+        assert_eq!((full.offset, full.buffer_len), (0, 8));
+
+        let trimmed = f.output_with(OutputMode::Trimmed);
+        assert_eq!(trimmed.data, [0, 0, 0, 2, 16]);
+        assert_eq!(trimmed.to_buffer(), full.data);
+        assert_eq!(f.output(), trimmed.data);
+
+        let pages = f.output_with(OutputMode::Pages);
+        assert_eq!(pages.data, [0, 2, 16, 0]);
+        assert_eq!(pages.pages(2), 1..3);
+        assert_eq!(pages.to_buffer(), full.data);
+
+        // nothing is kept of an empty frame
+        let empty = Frame::empty(2, 8);
+        assert!(empty.output_with(OutputMode::Pages).data.is_empty());
+        assert_eq!(empty.output_with(OutputMode::Trimmed).to_buffer(), [0, 0]);
+    }
+
+    #[test]
+    fn test_diff() {
+        let base = frame("#. .# .. .. .. .. .. ..");
+        let other = frame(".. .# .. .. .. .. .. ..");
+        let mut diff = base.diff(&other).unwrap();
+        assert_eq!(diff, [1, 0]);
+        crate::undiff(&base.output_with(OutputMode::Full).data, &mut diff);
+        assert_eq!(Frame::new(2, 8, &diff).unwrap(), other);
+
+        // the second frame would be trimmed to a single byte by `output`
+        assert_eq!(other.diff(&base).unwrap().len(), 2);
+        assert_eq!(
+            base.diff(&Frame::empty(2, 16)),
+            Err(crate::DiffError::DimensionMismatch {
+                base: (2, 8),
+                other: (2, 16)
+            })
+        );
+    }
 }
@@ -0,0 +1,603 @@
+use crate::emit::{CEmitter, Emitter};
+use crate::{Frame, OutputMode};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// how the base frame, that every other frame is diffed against, is chosen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BaseStrategy {
+    /// no base, every frame is only compressed
+    None,
+    /// every byte of the base is the most common byte at this position, see
+    /// `find_suboptimal_base_frame`
+    Suboptimal,
+    /// use the frame giving the smallest total size, see `base_frame::generate_base_frame`
+    Best,
+    /// use the specified frame
+    Frame(String),
+}
+
+impl std::str::FromStr for BaseStrategy {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(BaseStrategy::None),
+            "suboptimal" => Ok(BaseStrategy::Suboptimal),
+            "best" => Ok(BaseStrategy::Best),
+            s if s.starts_with("frame:") => {
+                Ok(BaseStrategy::Frame(s["frame:".len()..].to_string()))
+            }
+            s => Err(format!(
+                "unknown base strategy `{}`, expected `none`, `suboptimal`, `best` or `frame:NAME`",
+                s
+            )
+            .into()),
+        }
+    }
+}
+
+/// how the bytes of the frames are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    /// the run-length encoding of `compress`
+    Rle,
+    /// the bytes are stored as is
+    Raw,
+}
+
+impl Codec {
+    /// encode the bytes of a frame
+    pub fn encode(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Codec::Rle => crate::compress(data),
+            Codec::Raw => data.to_vec(),
+        }
+    }
+
+    /// decode the bytes of a frame, this is the reverse of `encode`
+    pub fn decode(self, data: &[u8]) -> Result<Vec<u8>, crate::UncompressError> {
+        match self {
+            Codec::Rle => crate::try_uncompress(data),
+            Codec::Raw => Ok(data.to_vec()),
+        }
+    }
+}
+
+impl std::str::FromStr for Codec {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rle" => Ok(Codec::Rle),
+            "raw" => Ok(Codec::Raw),
+            s => Err(format!("unknown codec `{}`, expected `rle` or `raw`", s).into()),
+        }
+    }
+}
+
+/// the result of the encoding of a set of frames
+#[derive(Debug, Clone)]
+pub struct Encoded {
+    /// the size of the buffer needed to display a frame
+    pub buffer_len: usize,
+    /// the base frame, if any
+    pub base: Option<Vec<u8>>,
+    /// the name of the frames associated with their compressed (and diffed) data
+    pub frames: Vec<(String, Vec<u8>)>,
+}
+
+impl Encoded {
+    /// total size of all the arrays, base frame included
+    pub fn total_size(&self) -> usize {
+        self.base.as_ref().map_or(0, |base| base.len())
+            + self
+                .frames
+                .iter()
+                .map(|(_, data)| data.len())
+                .sum::<usize>()
+    }
+
+    /// total size of the arrays really stored, the duplicated frames being aliases, see
+    /// `duplicates`
+    pub fn stored_size(&self) -> usize {
+        let duplicated: usize = self
+            .duplicates()
+            .iter()
+            .filter_map(|(name, _)| self.frames.iter().find(|(frame, _)| frame == name))
+            .map(|(_, data)| data.len())
+            .sum();
+        self.total_size() - duplicated
+    }
+
+    /// the frames whose data is identical to the data of a previous frame, with the name of the
+    /// first frame having this data
+    pub fn duplicates(&self) -> Vec<(String, String)> {
+        self.frames
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (name, data))| {
+                self.frames[..i]
+                    .iter()
+                    .find(|(_, previous)| previous == data)
+                    .map(|(target, _)| (name.clone(), target.clone()))
+            })
+            .collect()
+    }
+
+    /// write the base as `base` and every frame as `prefix` followed by its name in uppercase,
+    /// the duplicated frames are aliases of the first identical one
+    pub fn emit(
+        &self,
+        out: &mut dyn Write,
+        emitter: &mut dyn Emitter,
+        base: &str,
+        prefix: &str,
+    ) -> std::io::Result<()> {
+        let array_name = |name: &str| format!("{}{}", prefix, name.to_uppercase());
+        if let Some(data) = &self.base {
+            emitter.array(out, base, data)?;
+        }
+        let duplicates = self.duplicates();
+        for (name, data) in &self.frames {
+            match duplicates.iter().find(|(duplicate, _)| duplicate == name) {
+                Some((_, target)) => {
+                    emitter.alias(out, &array_name(name), &array_name(target), data.len())?
+                }
+                None => emitter.array(out, &array_name(name), data)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// the C module `name` of the frames of `width` × `height` pixels: the header has the
+    /// dimensions, the number of frames and the index of every frame in the `FRAMES` and
+    /// `FRAME_LENGTHS` tables, and the source has the arrays written by `emitter`, see `emit`.
+    /// The names of the module, of the arrays and of the frames must be valid C identifiers
+    pub fn module(
+        &self,
+        name: &str,
+        (width, height): (usize, usize),
+        base: &str,
+        prefix: &str,
+        emitter: &mut CEmitter,
+    ) -> Result<Module, Box<dyn std::error::Error>> {
+        check_c_identifier(name)?;
+        check_c_identifier(&format!("{}{}", emitter.style.prefix, base))?;
+        let arrays: Vec<String> = self
+            .frames
+            .iter()
+            .map(|(name, _)| format!("{}{}", emitter.style.prefix, prefix) + &name.to_uppercase())
+            .collect();
+        for ((name, _), array) in self.frames.iter().zip(&arrays) {
+            check_c_identifier(array)
+                .and_then(|_| check_c_identifier(&format!("FRAME_INDEX_{}", name.to_uppercase())))
+                .map_err(|e| format!("the frame `{}` can't be used in C: {}", name, e))?;
+        }
+
+        let mut header = format!(
+            "/* generated by kyria-oled, do not edit */
+#pragma once
+
+#include <stdint.h>
+
+#define FRAME_COUNT {count}
+#define FRAME_WIDTH {width}
+#define FRAME_HEIGHT {height}
+#define FRAME_BUFFER_SIZE {buffer_len}
+
+",
+            count = self.frames.len(),
+            width = width,
+            height = height,
+            buffer_len = self.buffer_len
+        );
+        for (i, (name, _)) in self.frames.iter().enumerate() {
+            header.push_str(&format!(
+                "#define FRAME_INDEX_{} {}
+",
+                name.to_uppercase(),
+                i
+            ));
+        }
+        header.push('\n');
+        if self.base.is_some() {
+            header.push_str("/* every frame is diffed against this one */\n");
+            header.push_str("extern const uint8_t *const FRAME_BASE;\n");
+        }
+        header.push_str("extern const uint8_t *const FRAMES[FRAME_COUNT];\n");
+        header.push_str("extern const uint16_t FRAME_LENGTHS[FRAME_COUNT];\n");
+
+        let mut source = format!(
+            "/* generated by kyria-oled, do not edit, see {}.h */\n",
+            name
+        );
+        if emitter.qualifier == "PROGMEM" {
+            source.push_str("#include \"progmem.h\"\n");
+        }
+        source.push_str(&format!("#include \"{}.h\"\n\n", name));
+        let mut arrays_c = Vec::new();
+        self.emit(&mut arrays_c, emitter, base, prefix)?;
+        source.push_str(&String::from_utf8(arrays_c)?);
+        source.push('\n');
+        if self.base.is_some() {
+            source.push_str(&format!(
+                "const uint8_t *const FRAME_BASE = {}{};\n",
+                emitter.style.prefix, base
+            ));
+        }
+        source.push_str(&format!(
+            "const uint8_t *const FRAMES[FRAME_COUNT] = {{ {} }};\n",
+            arrays.join(", ")
+        ));
+        source.push_str(&format!(
+            "const uint16_t FRAME_LENGTHS[FRAME_COUNT] = {{ {} }};\n",
+            arrays
+                .iter()
+                .map(|array| format!("sizeof({})", array))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
+        Ok(Module {
+            name: name.to_string(),
+            header,
+            source,
+        })
+    }
+}
+
+/// a generated C module, a header and its source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+    /// the name of the files, without extension
+    pub name: String,
+    pub header: String,
+    pub source: String,
+}
+
+impl Module {
+    /// write `<name>.h` and `<name>.c` in `dir` and return their paths
+    pub fn save(&self, dir: &Path) -> std::io::Result<(PathBuf, PathBuf)> {
+        let header = dir.join(format!("{}.h", self.name));
+        let source = dir.join(format!("{}.c", self.name));
+        std::fs::write(&header, &self.header)?;
+        std::fs::write(&source, &self.source)?;
+        Ok((header, source))
+    }
+}
+
+/// the C function `function(const uint8_t *data, uint16_t size)` decoding a frame compressed
+/// with `compress` in the `current` array, and undiffing it against `base` if there's one. The
+/// bytes are read unsigned so it doesn't depend on the signedness of `char`, nothing is written
+/// past the end of `current`. With `progmem` the arrays are read with `pgm_read_byte`
+pub fn decoder_c(function: &str, current: &str, base: Option<&str>, progmem: bool) -> String {
+    let read = |address: &str| {
+        if progmem {
+            format!("pgm_read_byte({})", address)
+        } else {
+            format!("*({})", address)
+        }
+    };
+    let undiff = match base {
+        Some(base) => format!(
+            "
+    for (uint16_t i = 0; i < sizeof({current}); i++) {{
+        {current}[i] = {read} - (uint8_t){current}[i];
+    }}",
+            current = current,
+            read = read(&format!("{} + i", base))
+        ),
+        None => String::new(),
+    };
+
+    format!(
+        "
+/* decode a frame in {current}, the bytes are read unsigned so it doesn't depend on the
+ * signedness of char, and nothing is written past the end of the buffer */
+static void {function}(const uint8_t *data, uint16_t size) {{
+    uint16_t pos = 0;
+    for (uint16_t i = 0; i < size; i++) {{
+        uint8_t control = {read_control};
+        uint8_t n = control & 0x7F;
+        if (control & 0x80) {{
+            while (n--) {{
+                uint8_t value = {read_value};
+                if (pos < sizeof({current})) {{
+                    {current}[pos++] = value;
+                }}
+            }}
+        }} else {{
+            uint8_t value = {read_value};
+            while (n-- && pos < sizeof({current})) {{
+                {current}[pos++] = value;
+            }}
+        }}
+    }}{undiff}
+}}
+",
+        function = function,
+        current = current,
+        read_control = read("data + i"),
+        read_value = read("data + ++i"),
+        undiff = undiff
+    )
+}
+
+/// check that a name can be used as a C identifier
+pub fn check_c_identifier(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => (),
+        _ => return Err(format!("`{}` is not a valid C identifier", name).into()),
+    }
+    if chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        Ok(())
+    } else {
+        Err(format!("`{}` is not a valid C identifier", name).into())
+    }
+}
+
+/// a frame replaced by a previous frame identical to it, or close enough
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Merge {
+    pub frame: String,
+    /// the frame it was replaced by
+    pub into: String,
+    /// the number of pixels that differ between the two frames
+    pub pixels: usize,
+}
+
+impl std::fmt::Display for Merge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.pixels {
+            0 => write!(f, "`{}` is identical to `{}`", self.frame, self.into),
+            1 => write!(
+                f,
+                "`{}` merged into `{}`, 1 pixel differs",
+                self.frame, self.into
+            ),
+            n => write!(
+                f,
+                "`{}` merged into `{}`, {} pixels differ",
+                self.frame, self.into, n
+            ),
+        }
+    }
+}
+
+/// replace every frame differing by at most `tolerance` pixels from a previous frame by this
+/// frame, so they are encoded to the same payload and become aliases, see `Encoded::duplicates`.
+/// A frame is only merged into a frame that was not merged itself, so the errors don't add up
+pub fn merge_frames(frames: &mut [(String, Frame)], tolerance: usize) -> Vec<Merge> {
+    let mut merges = Vec::new();
+    for i in 0..frames.len() {
+        let (previous, rest) = frames.split_at_mut(i);
+        let (name, frame) = &mut rest[0];
+        let closest = previous
+            .iter()
+            .filter(|(kept, _)| !merges.iter().any(|merge: &Merge| &merge.frame == kept))
+            .filter(|(_, kept)| kept.dimensions() == frame.dimensions())
+            .map(|(kept, kept_frame)| {
+                let pixels = crate::verify::count_different_pixels(kept_frame, frame);
+                (kept, kept_frame, pixels)
+            })
+            .filter(|(_, _, pixels)| *pixels <= tolerance)
+            .min_by_key(|(_, _, pixels)| *pixels);
+        if let Some((kept, kept_frame, pixels)) = closest {
+            merges.push(Merge {
+                frame: name.clone(),
+                into: kept.clone(),
+                pixels,
+            });
+            *frame = kept_frame.clone();
+        }
+    }
+    merges
+}
+
+/// compress all the frames with the specified base strategy
+pub fn encode(
+    frames: &[(String, Frame)],
+    base: &BaseStrategy,
+) -> Result<Encoded, Box<dyn std::error::Error>> {
+    encode_with_codec(frames, base, Codec::Rle)
+}
+
+/// encode all the frames with the specified base strategy and codec
+pub fn encode_with_codec(
+    frames: &[(String, Frame)],
+    base: &BaseStrategy,
+    codec: Codec,
+) -> Result<Encoded, Box<dyn std::error::Error>> {
+    if frames.is_empty() {
+        return Err("there is no frame to encode".into());
+    }
+    if frames
+        .windows(2)
+        .any(|v| v[0].1.dimensions() != v[1].1.dimensions())
+    {
+        return Err("All the frames need to have the same dimensions!".into());
+    }
+    let (width, height) = frames[0].1.dimensions();
+    Frame::check_dimensions(width, height)?;
+    let buffer_len = width * height / 8;
+
+    let outputs: Vec<Vec<u8>> = frames
+        .iter()
+        .map(|(_, frame)| frame.output_with(OutputMode::Full).data)
+        .collect();
+
+    let base = match base {
+        BaseStrategy::None => None,
+        BaseStrategy::Suboptimal => Some(crate::find_suboptimal_base_frame(&outputs)),
+        BaseStrategy::Best => Some(crate::base_frame::generate_base_frame(&outputs)),
+        BaseStrategy::Frame(name) => {
+            let idx = frames
+                .iter()
+                .position(|(frame, _)| frame == name)
+                .ok_or_else(|| format!("the base frame `{}` does not exist", name))?;
+            Some(outputs[idx].clone())
+        }
+    };
+
+    let frames = frames
+        .iter()
+        .zip(&outputs)
+        .map(|((name, _), output)| {
+            let data = match &base {
+                Some(base) => codec.encode(&crate::diff(base, output)),
+                None => codec.encode(output),
+            };
+            (name.clone(), data)
+        })
+        .collect();
+
+    Ok(Encoded {
+        buffer_len,
+        base,
+        frames,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bongo_cat() -> Vec<(String, Frame)> {
+        crate::bongo_cat(&["idle1", "idle3", "idle4", "tap1"])
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let frames = bongo_cat();
+        for strategy in &[
+            BaseStrategy::None,
+            BaseStrategy::Suboptimal,
+            BaseStrategy::Best,
+            BaseStrategy::Frame("idle4".to_string()),
+        ] {
+            let encoded = encode(&frames, strategy).unwrap();
+            assert_eq!(encoded.buffer_len, 640);
+
+            for ((_, frame), (_, data)) in frames.iter().zip(&encoded.frames) {
+                let mut current = vec![0; encoded.buffer_len];
+                crate::uncompress2(data, &mut current);
+                if let Some(base) = &encoded.base {
+                    crate::undiff(base, &mut current);
+                }
+                assert_eq!(
+                    Frame::new(128, 40, &current).unwrap().output(),
+                    frame.output()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_raw_codec() {
+        let frames = bongo_cat();
+        let encoded = encode_with_codec(&frames, &BaseStrategy::None, Codec::Raw).unwrap();
+        for ((_, frame), (_, data)) in frames.iter().zip(&encoded.frames) {
+            assert_eq!(data, &frame.output_with(OutputMode::Full).data);
+        }
+        assert_eq!("rle".parse::<Codec>().unwrap(), Codec::Rle);
+        assert!("lz4".parse::<Codec>().is_err());
+    }
+
+    #[test]
+    fn test_duplicates_and_module() {
+        let mut frames = bongo_cat();
+        frames.insert(1, ("idle2".to_string(), frames[0].1.clone()));
+        let encoded = encode(&frames, &BaseStrategy::Suboptimal).unwrap();
+        assert_eq!(
+            encoded.duplicates(),
+            vec![("idle2".to_string(), "idle1".to_string())]
+        );
+        assert_eq!(
+            encoded.stored_size(),
+            encoded.total_size() - encoded.frames[1].1.len()
+        );
+
+        let mut emitter = CEmitter::default();
+        let module = encoded
+            .module("bongo", (128, 40), "BASE", "F_", &mut emitter)
+            .unwrap();
+        assert!(module.header.contains("#define FRAME_COUNT 5\n"));
+        assert!(module.header.contains("#define FRAME_BUFFER_SIZE 640\n"));
+        assert!(module.header.contains("#define FRAME_INDEX_IDLE2 1\n"));
+        assert!(module.source.contains("#define F_IDLE2 F_IDLE1\n"));
+        assert!(module.source.contains(
+            "const uint8_t *const FRAMES[FRAME_COUNT] = { F_IDLE1, F_IDLE2, F_IDLE3, F_IDLE4, \
+             F_TAP1 };"
+        ));
+        assert!(module
+            .source
+            .contains("const uint16_t FRAME_LENGTHS[FRAME_COUNT] = { sizeof(F_IDLE1), "));
+
+        let arrays = crate::parse::parse_arrays(&module.source).unwrap();
+        assert_eq!(arrays.len(), 6);
+        assert_eq!(arrays[2].name, "F_IDLE2");
+        assert_eq!(arrays[2].data, encoded.frames[0].1);
+
+        // every name ends up in the C
+        assert!(encoded
+            .module("bongo", (128, 40), "BASE", "F-", &mut emitter)
+            .is_err());
+        assert!(encoded
+            .module("bongo", (128, 40), "BASE", "", &mut emitter)
+            .is_ok());
+        let mut encoded = encoded;
+        encoded.frames[1].0 = "frame-01".to_string();
+        assert!(encoded
+            .module("bongo", (128, 40), "BASE", "F_", &mut emitter)
+            .is_err());
+    }
+
+    #[test]
+    fn test_merge_frames() {
+        let mut frames = bongo_cat();
+        let mut close = frames[0].1.clone();
+        close.set(0, 0, true);
+        close.set(1, 0, true);
+        frames.push(("idle2".to_string(), frames[0].1.clone()));
+        frames.push(("close".to_string(), close.clone()));
+
+        let mut exact = frames.clone();
+        let merges = merge_frames(&mut exact, 0);
+        assert_eq!(
+            merges,
+            vec![Merge {
+                frame: "idle2".to_string(),
+                into: "idle1".to_string(),
+                pixels: 0
+            }]
+        );
+        assert_eq!(exact[5].1, close);
+        assert_eq!(merges[0].to_string(), "`idle2` is identical to `idle1`");
+
+        let merges = merge_frames(&mut frames, 2);
+        assert_eq!(merges.len(), 2);
+        assert_eq!(merges[1].into, "idle1");
+        assert_eq!(merges[1].pixels, 2);
+        assert_eq!(frames[5].1, frames[0].1);
+        assert_eq!(
+            merges[1].to_string(),
+            "`close` merged into `idle1`, 2 pixels differ"
+        );
+
+        let encoded = encode(&frames, &BaseStrategy::Suboptimal).unwrap();
+        assert_eq!(encoded.duplicates().len(), 2);
+    }
+
+    #[test]
+    fn test_unknown_base_frame() {
+        assert!(encode(&bongo_cat(), &BaseStrategy::Frame("prep".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_frames_not_in_pages() {
+        let frames = vec![("twelve".to_string(), Frame::empty(8, 12))];
+        assert!(encode(&frames, &BaseStrategy::None).is_err());
+    }
+}
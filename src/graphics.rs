@@ -0,0 +1,296 @@
+//! show frames as real bitmaps in the terminals supporting the Sixel or the Kitty graphics
+//! protocol.
+//!
+//! Every pixel of the frame is drawn as a square of `scale` × `scale` pixels of the terminal, in
+//! the colours of a `Palette`. There's no way to know for sure if a terminal supports a protocol
+//! without querying it, so `detect` only guesses from the environment.
+
+use crate::Frame;
+
+/// a graphics protocol of the terminals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Sixel,
+    Kitty,
+}
+
+impl std::str::FromStr for Protocol {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sixel" => Ok(Protocol::Sixel),
+            "kitty" => Ok(Protocol::Kitty),
+            s => Err(format!(
+                "unknown graphics protocol `{}`, expected `sixel` or `kitty`",
+                s
+            )
+            .into()),
+        }
+    }
+}
+
+/// the colours of the pixels, the pixels off are always black
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Palette {
+    White,
+    /// the cyan blue of the cheap OLED panels
+    Blue,
+}
+
+impl Palette {
+    /// the (off, on) colours in RGB
+    pub fn colors(self) -> ([u8; 3], [u8; 3]) {
+        match self {
+            Palette::White => ([0, 0, 0], [0xff, 0xff, 0xff]),
+            Palette::Blue => ([0, 0, 0], [0x33, 0xbb, 0xff]),
+        }
+    }
+}
+
+impl std::str::FromStr for Palette {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "white" => Ok(Palette::White),
+            "blue" => Ok(Palette::Blue),
+            s => Err(format!("unknown palette `{}`, expected `white` or `blue`", s).into()),
+        }
+    }
+}
+
+/// guess the graphics protocol of the terminal from the environment variables
+pub fn detect() -> Option<Protocol> {
+    detect_with(|name| std::env::var(name).ok())
+}
+
+fn detect_with(var: impl Fn(&str) -> Option<String>) -> Option<Protocol> {
+    let term = var("TERM").unwrap_or_default();
+    let program = var("TERM_PROGRAM").unwrap_or_default();
+    if term == "xterm-kitty"
+        || var("KITTY_WINDOW_ID").is_some()
+        || program == "WezTerm"
+        || program == "ghostty"
+    {
+        Some(Protocol::Kitty)
+    } else if term.contains("sixel")
+        || term.starts_with("foot")
+        || term.starts_with("mlterm")
+        || term == "contour"
+    {
+        Some(Protocol::Sixel)
+    } else {
+        None
+    }
+}
+
+/// encode a frame with `protocol`
+pub fn encode(frame: &Frame, protocol: Protocol, scale: usize, palette: Palette) -> String {
+    match protocol {
+        Protocol::Sixel => sixel(frame, scale, palette),
+        Protocol::Kitty => kitty(frame, scale, palette),
+    }
+}
+
+/// the pixel of the scaled frame
+fn scaled(frame: &Frame, scale: usize, x: usize, y: usize) -> bool {
+    frame.get(x / scale, y / scale) == Some(true)
+}
+
+/// the Sixel image of a frame. The image is made of bands of 6 rows, every band is drawn once
+/// per colour, and the repeated sixels are run-length encoded
+pub fn sixel(frame: &Frame, scale: usize, palette: Palette) -> String {
+    let scale = scale.max(1);
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    let (off, on) = palette.colors();
+    // the colours are in percents
+    let percent = |value: u8| (value as usize * 100 + 127) / 255;
+
+    let mut s = format!("\x1bPq\"1;1;{};{}", width, height);
+    for (i, color) in [off, on].iter().enumerate() {
+        s.push_str(&format!(
+            "#{};2;{};{};{}",
+            i,
+            percent(color[0]),
+            percent(color[1]),
+            percent(color[2])
+        ));
+    }
+
+    for band in 0..height.div_ceil(6) {
+        if band > 0 {
+            s.push('-');
+        }
+        for (i, color) in [false, true].iter().enumerate() {
+            if i > 0 {
+                s.push('$');
+            }
+            s.push_str(&format!("#{}", i));
+            let sixels: Vec<char> = (0..width)
+                .map(|x| {
+                    let bits = (0..6)
+                        .filter(|dy| {
+                            let y = band * 6 + dy;
+                            y < height && scaled(frame, scale, x, y) == *color
+                        })
+                        .fold(0, |bits, dy| bits | 1 << dy);
+                    (63 + bits) as u8 as char
+                })
+                .collect();
+            let mut x = 0;
+            while x < sixels.len() {
+                let run = sixels[x..].iter().take_while(|&&c| c == sixels[x]).count();
+                if run > 3 {
+                    s.push_str(&format!("!{}{}", run, sixels[x]));
+                } else {
+                    s.extend(std::iter::repeat_n(sixels[x], run));
+                }
+                x += run;
+            }
+        }
+    }
+    s.push_str("\x1b\\");
+    s
+}
+
+/// the Kitty graphics commands displaying a frame at the cursor. The image is sent as RGB with
+/// the id 1, so playing an animation replaces the previous frame instead of stacking images, and
+/// the terminal doesn't answer
+pub fn kitty(frame: &Frame, scale: usize, palette: Palette) -> String {
+    // the payload is sent in chunks of at most 4096 bytes
+    const CHUNK: usize = 4096;
+    let scale = scale.max(1);
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    let (off, on) = palette.colors();
+
+    let mut rgb = Vec::with_capacity(width * height * 3);
+    for y in 0..height {
+        for x in 0..width {
+            rgb.extend_from_slice(if scaled(frame, scale, x, y) {
+                &on
+            } else {
+                &off
+            });
+        }
+    }
+    let payload = base64(&rgb);
+
+    let mut s = String::new();
+    let chunks: Vec<&str> = payload
+        .as_bytes()
+        .chunks(CHUNK)
+        .map(|chunk| std::str::from_utf8(chunk).expect("base64 is ascii"))
+        .collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = (i + 1 < chunks.len()) as u8;
+        if i == 0 {
+            s.push_str(&format!(
+                "\x1b_Ga=T,f=24,s={},v={},i=1,q=2,m={};{}\x1b\\",
+                width, height, more, chunk
+            ));
+        } else {
+            s.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+    s
+}
+
+/// the standard base64 encoding, with padding
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut s = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (bytes[0] as usize) << 16 | (bytes[1] as usize) << 8 | bytes[2] as usize;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                s.push(ALPHABET[n >> (18 - 6 * i) & 0x3f] as char);
+            } else {
+                s.push('=');
+            }
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xff, 0xfe]), "//4=");
+    }
+
+    #[test]
+    fn test_detect() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            detect_with(env(&[("TERM", "xterm-kitty")])),
+            Some(Protocol::Kitty)
+        );
+        assert_eq!(
+            detect_with(env(&[
+                ("TERM", "xterm-256color"),
+                ("TERM_PROGRAM", "WezTerm")
+            ])),
+            Some(Protocol::Kitty)
+        );
+        assert_eq!(detect_with(env(&[("TERM", "foot")])), Some(Protocol::Sixel));
+        assert_eq!(detect_with(env(&[("TERM", "xterm-256color")])), None);
+        assert_eq!(detect_with(env(&[])), None);
+    }
+
+    #[test]
+    fn test_sixel() {
+        let mut frame = Frame::empty(2, 1);
+        frame.set(1, 0, true);
+        assert_eq!(
+            sixel(&frame, 1, Palette::White),
+            "\x1bPq\"1;1;2;1#0;2;0;0;0#1;2;100;100;100#0@?$#1?@\x1b\\"
+        );
+        // 4 columns of 6 rows, the runs of the same sixel are encoded
+        assert_eq!(
+            sixel(&frame, 3, Palette::White),
+            "\x1bPq\"1;1;6;3#0;2;0;0;0#1;2;100;100;100#0FFF???$#1???FFF\x1b\\"
+        );
+        let frame = Frame::empty(4, 7);
+        assert_eq!(
+            sixel(&frame, 1, Palette::Blue),
+            "\x1bPq\"1;1;4;7#0;2;0;0;0#1;2;20;73;100#0!4~$#1!4?-#0!4@$#1!4?\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_kitty() {
+        let mut frame = Frame::empty(1, 2);
+        frame.set(0, 1, true);
+        assert_eq!(
+            kitty(&frame, 1, Palette::White),
+            "\x1b_Ga=T,f=24,s=1,v=2,i=1,q=2,m=0;AAAA////\x1b\\"
+        );
+
+        // 3 chunks of base64
+        let frame = Frame::empty(64, 64);
+        let kitty = kitty(&frame, 1, Palette::Blue);
+        assert!(kitty.starts_with("\x1b_Ga=T,f=24,s=64,v=64,i=1,q=2,m=1;AAAA"));
+        assert_eq!(kitty.matches("\x1b_Gm=1;").count(), 2);
+        assert_eq!(kitty.matches("\x1b_Gm=0;").count(), 1);
+    }
+}
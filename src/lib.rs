@@ -1,6 +1,24 @@
+pub mod animation;
 pub mod base_frame;
+pub mod budget;
+pub mod cycles;
+mod draw;
+pub mod editor;
+pub mod emit;
+pub mod font;
 pub mod frame;
-pub use frame::Frame;
+pub mod generator;
+pub mod graphics;
+pub mod lossy;
+pub mod manifest;
+pub mod parse;
+pub mod qmk;
+pub mod scale;
+pub mod verify;
+pub mod wpm;
+pub mod zmk;
+pub use frame::{BlendMode, Edge, Frame, Output, OutputMode, RenderStyle, Rotation};
+pub use scale::{Filter, Fit};
 
 use std::collections::HashMap;
 
@@ -81,22 +99,76 @@ pub fn compress(data: &[u8]) -> Vec<u8> {
     res
 }
 
-/// uncompress a frame, this method mostly exists for testing purpose
+/// uncompress a frame, this method mostly exists for testing purpose.
+/// Panics if the data is truncated, see `try_uncompress`
 pub fn uncompress(data: &[u8]) -> Vec<u8> {
-    let mut iter = data.iter().copied();
+    try_uncompress(data).unwrap()
+}
+
+/// the error returned when compressed data ends before the bytes announced by a control byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UncompressError {
+    /// the control byte at `offset` announces a run but the repeated byte is missing
+    MissingRunValue { offset: usize },
+    /// the control byte at `offset` announces `expected` bytes but only `found` remain
+    MissingLiterals {
+        offset: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl std::fmt::Display for UncompressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UncompressError::MissingRunValue { offset } => write!(
+                f,
+                "truncated data: the run at offset {} has no byte to repeat",
+                offset
+            ),
+            UncompressError::MissingLiterals {
+                offset,
+                expected,
+                found,
+            } => write!(
+                f,
+                "truncated data: the control byte at offset {} announces {} bytes but only {} remain",
+                offset, expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for UncompressError {}
+
+/// same as `uncompress` but return an error instead of panicking on truncated data
+pub fn try_uncompress(data: &[u8]) -> Result<Vec<u8>, UncompressError> {
     let mut res = Vec::new();
+    let mut offset = 0;
 
-    while let Some(byte) = iter.next() {
-        let (mode, n) = ((byte >> 7) == 1, byte & 0b0111_1111);
+    while let Some(&byte) = data.get(offset) {
+        let (mode, n) = ((byte >> 7) == 1, (byte & 0b0111_1111) as usize);
+        let rest = &data[offset + 1..];
         if mode {
-            (0..n).for_each(|_| res.push(iter.next().unwrap()));
+            if rest.len() < n {
+                return Err(UncompressError::MissingLiterals {
+                    offset,
+                    expected: n,
+                    found: rest.len(),
+                });
+            }
+            res.extend_from_slice(&rest[..n]);
+            offset += n + 1;
         } else {
-            let next = iter.next().unwrap();
-            (0..n).for_each(|_| res.push(next));
+            let next = *rest
+                .first()
+                .ok_or(UncompressError::MissingRunValue { offset })?;
+            res.extend(std::iter::repeat_n(next, n));
+            offset += 2;
         }
     }
 
-    res
+    Ok(res)
 }
 
 /// uncompress a frame, this method mostly exists for testing purpose
@@ -129,62 +201,125 @@ pub fn uncompress2(data: &[u8], output: &mut [u8]) {
     }
 }
 
-/// return a vec representing the diff between two vectors
+/// return a vec representing the diff between two vectors.
+///
+/// The frames output by `Frame::output` are trimmed, so the shortest vector is padded with
+/// zeros and the diff is as long as the longest one
 pub fn diff(base: &[u8], other: &[u8]) -> Vec<u8> {
-    base.iter()
-        .zip(other)
-        .map(|(base, &other)| base.wrapping_sub(other))
+    let byte = |v: &[u8], i: usize| v.get(i).copied().unwrap_or(0);
+    (0..base.len().max(other.len()))
+        .map(|i| byte(base, i).wrapping_sub(byte(other, i)))
         .collect()
 }
 
-/// undiff two vecs wrote in a C way, it does the same thing as the diff method
+/// the error returned when two frames that must be diffed don't match
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffError {
+    /// the two slices don't have the same length
+    LengthMismatch { base: usize, other: usize },
+    /// the two frames don't have the same dimensions, as (width, height)
+    DimensionMismatch {
+        base: (usize, usize),
+        other: (usize, usize),
+    },
+}
+
+impl std::fmt::Display for DiffError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiffError::LengthMismatch { base, other } => write!(
+                f,
+                "length mismatch: the base is {} bytes but the other frame is {} bytes",
+                base, other
+            ),
+            DiffError::DimensionMismatch { base, other } => write!(
+                f,
+                "dimension mismatch: the base is {} × {} but the other frame is {} × {}",
+                base.0, base.1, other.0, other.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DiffError {}
+
+/// same as `diff` but the two slices must have the same length
+pub fn try_diff(base: &[u8], other: &[u8]) -> Result<Vec<u8>, DiffError> {
+    if base.len() != other.len() {
+        return Err(DiffError::LengthMismatch {
+            base: base.len(),
+            other: other.len(),
+        });
+    }
+    Ok(diff(base, other))
+}
+
+/// same as `undiff` but the two slices must have the same length instead of panicking.
+///
+/// This is stricter than `undiff`, which also accepts an `other` longer than `base` and leaves
+/// its end untouched: a frame buffer longer than the base would keep the end of the previous
+/// frame, so it's reported as a `LengthMismatch` too
+pub fn try_undiff(base: &[u8], other: &mut [u8]) -> Result<(), DiffError> {
+    if base.len() != other.len() {
+        return Err(DiffError::LengthMismatch {
+            base: base.len(),
+            other: other.len(),
+        });
+    }
+    undiff(base, other);
+    Ok(())
+}
+
+/// undiff two vecs wrote in a C way, it does the same thing as the diff method.
+/// `other` must be at least as long as `base`, like the frame buffer in C, see `try_undiff`
 pub fn undiff(base: &[u8], other: &mut [u8]) {
     for i in 0..base.len() {
         other[i] = base[i].wrapping_sub(other[i]);
     }
 }
 
-/// generate a ready-to-use frame from a base frame and a frame
-pub fn generate_from_base(base: &[u8], other: &[u8]) -> Vec<u8> {
-    compress(&diff(&base, &other))
+/// generate a ready-to-use frame from a base frame and a frame.
+///
+/// The C code undiffs the whole frame buffer with the base, so the base can't be shorter than
+/// the frame, use `OutputMode::Full` to output it. A shorter frame is padded with zeros.
+pub fn generate_from_base(base: &[u8], other: &[u8]) -> Result<Vec<u8>, DiffError> {
+    if other.len() > base.len() {
+        return Err(DiffError::LengthMismatch {
+            base: base.len(),
+            other: other.len(),
+        });
+    }
+    Ok(compress(&diff(base, other)))
 }
 
 /// print a rust slice as a C array.
 /// `varname` is the name of the array and `v` the slice
 pub fn print_slice_as_c_array(varname: &str, v: &[u8]) {
-    println!("static const uint8_t PROGMEM {}[{}] = {{", varname, v.len());
-    let mut col = 0;
-    for index in 0..v.len() - 1 {
-        let tmp = format!("{}, ", v[index]);
-        col += tmp.len();
-        if col < 80 {
-            print!("{}", tmp);
-        } else {
-            col = tmp.len();
-            print!("\n{}", tmp);
-        }
-    }
+    print!("{}", format_slice_as_c_array(varname, v));
+}
 
-    println!("{}\n}};", v.last().unwrap());
+/// same as `print_slice_as_c_array` but return the array instead of printing it
+pub fn format_slice_as_c_array(varname: &str, v: &[u8]) -> String {
+    format_c_array("PROGMEM", varname, v)
+}
+
+/// format a C array declared with `qualifier` between its type and its name, like `PROGMEM` for
+/// AVR, nothing is added when it's empty. See `emit::CEmitter` for the other options
+pub fn format_c_array(qualifier: &str, varname: &str, v: &[u8]) -> String {
+    let mut emitter = emit::CEmitter {
+        qualifier: qualifier.to_string(),
+        ..emit::CEmitter::default()
+    };
+    emit::format_array(&mut emitter, varname, v)
 }
 
 /// print a rust slice as a rust array.
 /// `varname` is the name of the array and `v` the slice
 pub fn print_slice_as_rust_array(varname: &str, v: &[u8]) {
-    println!("const {}: [u8; {}] = [", varname, v.len());
-    let mut col = 0;
-    for index in 0..v.len() - 1 {
-        let tmp = format!("{}, ", v[index]);
-        col += tmp.len();
-        if col < 80 {
-            print!("{}", tmp);
-        } else {
-            col = tmp.len();
-            print!("\n{}", tmp);
-        }
-    }
-
-    println!("{}\n];", v.last().unwrap());
+    print!(
+        "{}",
+        emit::format_array(&mut emit::RustEmitter::default(), varname, v)
+    );
 }
 
 pub fn find_suboptimal_base_frame(frames: &[Vec<u8>]) -> Vec<u8> {
@@ -200,9 +335,20 @@ pub fn find_suboptimal_base_frame(frames: &[Vec<u8>]) -> Vec<u8> {
                 })
                 .iter()
                 .max_by_key(|(_value, occurences)| *occurences)
+                .map(|(value, _occurences)| *value)
                 .unwrap()
-                .0
-                .clone()
+        })
+        .collect()
+}
+
+/// load the bongo cat frames used by the tests, named after their file
+#[cfg(test)]
+pub(crate) fn bongo_cat(names: &[&str]) -> Vec<(String, Frame)> {
+    names
+        .iter()
+        .map(|name| {
+            let path = format!("{}/bongo_cat_frames/{}", env!("CARGO_MANIFEST_DIR"), name);
+            (name.to_string(), Frame::create_from_file(&path).unwrap())
         })
         .collect()
 }
@@ -221,6 +367,25 @@ mod tests {
             diff(&[1, 2, 3, 4, 5], &[0, 0, -2_i8 as u8, 3, 3]),
             &[1, 2, 5, 1, 2]
         );
+        // the trimmed frames are padded with zeros
+        assert_eq!(diff(&[1, 2, 3], &[1]), &[0, 2, 3]);
+        assert_eq!(diff(&[1], &[1, 2]), &[0, -2_i8 as u8]);
+    }
+
+    #[test]
+    fn test_try_diff_undiff() {
+        let mut other = try_diff(&[1, 2, 3], &[1, 2, 5]).unwrap();
+        assert_eq!(other, &[0, 0, -2_i8 as u8]);
+        try_undiff(&[1, 2, 3], &mut other).unwrap();
+        assert_eq!(other, &[1, 2, 5]);
+
+        let mismatch = DiffError::LengthMismatch { base: 3, other: 1 };
+        assert_eq!(try_diff(&[1, 2, 3], &[1]), Err(mismatch));
+        assert_eq!(try_undiff(&[1, 2, 3], &mut [1]), Err(mismatch));
+        assert_eq!(
+            try_undiff(&[1], &mut [1, 2]),
+            Err(DiffError::LengthMismatch { base: 1, other: 2 })
+        );
     }
 
     #[test]
@@ -304,8 +469,55 @@ mod tests {
         8, 8, 23, 0, 15, 1, 2, 1, 15, 0, 15, 2, 5, 8,
     ];
 
+    #[test]
+    fn test_try_uncompress_truncated() {
+        assert_eq!(try_uncompress(&[2, 7, 0x82, 1, 2]), Ok(vec![7, 7, 1, 2]));
+        assert_eq!(
+            try_uncompress(&[2, 7, 5]),
+            Err(UncompressError::MissingRunValue { offset: 2 })
+        );
+        assert_eq!(
+            try_uncompress(&[0x83, 1, 2]),
+            Err(UncompressError::MissingLiterals {
+                offset: 0,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
     #[test]
     fn test_compress_uncompress() {
         assert_eq!(uncompress(&compress(&TEST_FRAME)), &TEST_FRAME);
     }
+
+    #[test]
+    fn test_generate_from_base_undiff() {
+        let mut current = uncompress(&generate_from_base(&TEST_FRAME, &TEST_FRAME2).unwrap());
+        undiff(&TEST_FRAME, &mut current);
+        assert_eq!(current, &TEST_FRAME2);
+    }
+
+    #[test]
+    fn test_generate_from_base_trimmed() {
+        // a frame trimmed shorter than the base is padded with zeros, so the end of the buffer
+        // is zeros once undiffed instead of the end of the base
+        let trimmed = &TEST_FRAME2[..400];
+        let mut current = uncompress(&generate_from_base(&TEST_FRAME, trimmed).unwrap());
+        assert_eq!(current.len(), TEST_FRAME.len());
+        undiff(&TEST_FRAME, &mut current);
+        assert_eq!(&current[..400], trimmed);
+        assert!(current[400..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_generate_from_base_short_base() {
+        assert_eq!(
+            generate_from_base(&TEST_FRAME[..400], &TEST_FRAME2),
+            Err(DiffError::LengthMismatch {
+                base: 400,
+                other: TEST_FRAME2.len()
+            })
+        );
+    }
 }
@@ -0,0 +1,316 @@
+//! lossy encoding: flip a few pixels of every frame so `compress` finds longer runs.
+//!
+//! A byte of the frame is replaced by one of its neighbours when it saves compressed bytes, the
+//! replacement saving the most bytes per flipped pixel first, until the pixel budget of the
+//! frame is spent. With a base frame the bytes are the ones of the diff, so replacing a byte by
+//! 0 snaps its pixels to the base.
+
+use crate::generator::{encode, BaseStrategy, Encoded};
+use crate::Frame;
+
+/// how much the frames can be altered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    /// at most this number of flipped pixels per frame
+    Pixels(usize),
+    /// a number of flipped pixels per frame giving arrays of at most this size in total, base
+    /// frame included. It's found by a binary search, and the size doesn't always decrease
+    /// when the budget grows, so a smaller budget may fit too
+    TotalSize(usize),
+}
+
+/// the result of a lossy encoding
+#[derive(Debug, Clone)]
+pub struct Lossy {
+    pub encoded: Encoded,
+    /// the frames as they are displayed once decoded
+    pub frames: Vec<(String, Frame)>,
+    /// the number of flipped pixels allowed per frame, the one given or the one found for the
+    /// target size
+    pub pixels: usize,
+}
+
+/// encode the frames with at most `budget` altered pixels, the base frame is chosen on the
+/// original frames and is not altered
+pub fn encode_lossy(
+    frames: &[(String, Frame)],
+    base: &BaseStrategy,
+    budget: Budget,
+) -> Result<Lossy, Box<dyn std::error::Error>> {
+    let lossless = encode(frames, base)?;
+    match budget {
+        Budget::Pixels(pixels) => Ok(reduce_all(frames, &lossless, pixels)),
+        Budget::TotalSize(size) => {
+            let (width, height) = frames[0].1.dimensions();
+            let fits = |lossy: &Lossy| lossy.encoded.total_size() <= size;
+
+            let exact = reduce_all(frames, &lossless, 0);
+            if fits(&exact) {
+                return Ok(exact);
+            }
+
+            // the size doesn't always decrease with the budget, but close enough for a search
+            let mut high = 1;
+            let mut best = loop {
+                let lossy = reduce_all(frames, &lossless, high);
+                if fits(&lossy) {
+                    break lossy;
+                }
+                if high >= width * height {
+                    return Err(format!(
+                        "the arrays can't be smaller than {} bytes, {} bytes were asked",
+                        lossy.encoded.total_size(),
+                        size
+                    )
+                    .into());
+                }
+                high = (high * 2).min(width * height);
+            };
+            let mut low = high / 2;
+            while high - low > 1 {
+                let middle = (low + high) / 2;
+                let lossy = reduce_all(frames, &lossless, middle);
+                if fits(&lossy) {
+                    high = middle;
+                    best = lossy;
+                } else {
+                    low = middle;
+                }
+            }
+            Ok(best)
+        }
+    }
+}
+
+fn reduce_all(frames: &[(String, Frame)], lossless: &Encoded, pixels: usize) -> Lossy {
+    let base = lossless.base.as_deref();
+    let mut encoded = lossless.clone();
+    let mut altered = Vec::new();
+    for ((name, frame), (_, data)) in frames.iter().zip(&mut encoded.frames) {
+        let output = frame.output_with(crate::OutputMode::Full).data;
+        let stored = match base {
+            Some(base) => crate::diff(base, &output),
+            None => output,
+        };
+        let stored = reduce(&stored, base, pixels);
+        *data = crate::compress(&stored);
+
+        let mut decoded = stored;
+        if let Some(base) = base {
+            crate::undiff(base, &mut decoded);
+        }
+        let (width, height) = frame.dimensions();
+        let decoded = Frame::new(width, height, &decoded).expect("same dimensions");
+        altered.push((name.clone(), decoded));
+    }
+    Lossy {
+        encoded,
+        frames: altered,
+        pixels,
+    }
+}
+
+/// replace bytes of `stored`, the frame or its diff with `base`, by their neighbours while it
+/// makes the compressed data smaller and at most `pixels` pixels are flipped
+pub fn reduce(stored: &[u8], base: Option<&[u8]>, pixels: usize) -> Vec<u8> {
+    let displayed = |i: usize, byte: u8| match base {
+        Some(base) => base[i].wrapping_sub(byte),
+        None => byte,
+    };
+    let original: Vec<u8> = (0..stored.len()).map(|i| displayed(i, stored[i])).collect();
+    // the cost is counted against the original frame, so a pixel flipped back is free again
+    let cost = |i: usize, byte: u8| (displayed(i, byte) ^ original[i]).count_ones() as usize;
+    let mut stored = stored.to_vec();
+    let mut spent = 0;
+
+    loop {
+        // (saved bytes, flipped pixels, position, value)
+        let mut best: Option<(usize, usize, usize, u8)> = None;
+        for i in 0..stored.len() {
+            let neighbours = [
+                i.checked_sub(1).map(|j| stored[j]),
+                stored.get(i + 1).copied(),
+            ];
+            for value in neighbours.iter().flatten().copied() {
+                if value == stored[i] {
+                    continue;
+                }
+                let flipped = spent + cost(i, value) - cost(i, stored[i]);
+                if flipped > pixels {
+                    continue;
+                }
+                let saved = match saved_bytes(&mut stored, i, value) {
+                    Some(saved) => saved,
+                    None => continue,
+                };
+                let added = flipped.saturating_sub(spent).max(1);
+                // the most bytes saved per flipped pixel
+                let better = best.is_none_or(|(best_saved, best_added, _, _)| {
+                    saved * best_added > best_saved * added
+                });
+                if better {
+                    best = Some((saved, added, i, value));
+                }
+            }
+        }
+        match best {
+            Some((_, _, i, value)) => {
+                spent = spent + cost(i, value) - cost(i, stored[i]);
+                stored[i] = value;
+            }
+            None => return stored,
+        }
+    }
+}
+
+/// the number of bytes saved by `compress` when `data[i]` is replaced by `value`, if any.
+/// A maximal run of at least 2 bytes always starts a new control byte, so only the data
+/// between the last such run before `i` and the first one after is compressed again
+fn saved_bytes(data: &mut [u8], i: usize, value: u8) -> Option<usize> {
+    let start = (1..i)
+        .rev()
+        .find(|&j| data[j - 1] == data[j])
+        .map_or(0, |j| {
+            (0..j)
+                .rev()
+                .find(|&k| data[k] != data[j])
+                .map_or(0, |k| k + 1)
+        });
+    let end = (i + 2..data.len().saturating_sub(1))
+        .find(|&j| data[j - 1] != data[j] && data[j] == data[j + 1])
+        .unwrap_or(data.len());
+
+    let before = compressed_len(&data[start..end]);
+    let previous = std::mem::replace(&mut data[i], value);
+    let after = compressed_len(&data[start..end]);
+    data[i] = previous;
+    before.checked_sub(after).filter(|&saved| saved > 0)
+}
+
+/// the length of `compress(data)`, without compressing it
+pub fn compressed_len(data: &[u8]) -> usize {
+    // the literal bytes are grouped by 127 behind a control byte
+    let literals = |count: usize| count + count.div_ceil(127);
+    let mut len = 0;
+    let mut singles = 0;
+    let mut i = 0;
+    while i < data.len() {
+        let run = data[i..]
+            .iter()
+            .take_while(|&&byte| byte == data[i])
+            .count();
+        // a run is split in runs of at most 127 bytes
+        if run >= 127 {
+            len += literals(singles) + 2 * (run / 127);
+            singles = 0;
+        }
+        match run % 127 {
+            0 => (),
+            1 => singles += 1,
+            _ => {
+                len += literals(singles) + 2;
+                singles = 0;
+            }
+        }
+        i += run;
+    }
+    len + literals(singles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::count_different_pixels;
+
+    fn bongo_cat() -> Vec<(String, Frame)> {
+        crate::bongo_cat(&["idle1", "prep", "tap1"])
+    }
+
+    #[test]
+    fn test_reduce() {
+        // an isolated pixel in a run of zeros
+        let stored = [0, 0, 0, 0, 1, 0, 0, 0, 0];
+        assert_eq!(reduce(&stored, None, 0), stored);
+        assert_eq!(reduce(&stored, None, 1), [0; 9]);
+
+        // with a base the cost is counted on the displayed pixels
+        let base = [0xff; 9];
+        assert_eq!(reduce(&stored, Some(&base), 1), [0; 9]);
+    }
+
+    #[test]
+    fn test_compressed_len_and_saved_bytes() {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..500 {
+            let len = (next() % 600) as usize;
+            let mut data = Vec::new();
+            while data.len() < len {
+                let run = (next() % 300) as usize % (1 + next() as usize % 4 * 100);
+                let byte = (next() % 3) as u8;
+                data.resize((data.len() + run + 1).min(len), byte);
+            }
+            assert_eq!(compressed_len(&data), crate::compress(&data).len());
+
+            // the window gives the same saving as compressing everything again
+            if data.is_empty() {
+                continue;
+            }
+            let i = next() as usize % data.len();
+            let value = (next() % 3) as u8;
+            let mut changed = data.clone();
+            changed[i] = value;
+            let saved = crate::compress(&data)
+                .len()
+                .checked_sub(crate::compress(&changed).len())
+                .filter(|&saved| saved > 0);
+            assert_eq!(saved_bytes(&mut data, i, value), saved);
+        }
+    }
+
+    #[test]
+    fn test_pixel_budget() {
+        let frames = bongo_cat();
+        let base = BaseStrategy::Frame("idle1".to_string());
+        let lossless = encode(&frames, &base).unwrap();
+        let lossy = encode_lossy(&frames, &base, Budget::Pixels(0)).unwrap();
+        assert_eq!(lossy.encoded.frames, lossless.frames);
+        assert_eq!(lossy.frames, frames);
+
+        let lossy = encode_lossy(&frames, &base, Budget::Pixels(20)).unwrap();
+        assert!(lossy.encoded.total_size() < lossless.total_size());
+        assert_eq!(lossy.encoded.base, lossless.base);
+        for ((_, original), (_, altered)) in frames.iter().zip(&lossy.frames) {
+            assert!(count_different_pixels(original, altered) <= 20);
+        }
+        // the altered frames are what the firmware displays
+        let mismatches =
+            crate::verify::verify(&lossy.frames, &lossy.encoded, crate::generator::Codec::Rle);
+        assert!(mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_total_size() {
+        let frames = bongo_cat();
+        let lossless = encode(&frames, &BaseStrategy::None).unwrap();
+        let target = lossless.total_size() - 40;
+        let lossy = encode_lossy(&frames, &BaseStrategy::None, Budget::TotalSize(target)).unwrap();
+        assert!(lossy.encoded.total_size() <= target);
+        assert!(lossy.pixels > 0);
+        // a smaller budget doesn't fit
+        let smaller = encode_lossy(
+            &frames,
+            &BaseStrategy::None,
+            Budget::Pixels(lossy.pixels - 1),
+        )
+        .unwrap();
+        assert!(smaller.encoded.total_size() > target);
+
+        assert!(encode_lossy(&frames, &BaseStrategy::None, Budget::TotalSize(10)).is_err());
+    }
+}
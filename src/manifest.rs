@@ -0,0 +1,657 @@
+//! An animation manifest describes the frames of an animation, how they are played and how the
+//! code is generated.
+//!
+//! It's a simple line based format, everything following a `#` is a comment:
+//!
+//! ```text
+//! size 128x40
+//! # every frame is displayed 200ms unless specified otherwise
+//! duration 200
+//! base frame:idle4
+//! # the frames differing by at most 2 pixels from a previous frame are replaced by it
+//! tolerance 2
+//! # the frames can be adapted to a panel of another size with `fit`, `crop` or `letterbox`
+//! panel 128x32 fit
+//! # the `oled_rotation_t` of the keyboard, the frames are drawn as seen on the unrotated panel
+//! rotation 270
+//!
+//! frame idle1 bongo_cat_frames/idle1
+//! frame idle3 bongo_cat_frames/idle3
+//! # idle2 is the same art as idle1, no need to store it twice
+//! alias idle2 idle1
+//!
+//! # `*N` repeats a frame and `@MS` overrides its duration
+//! sequence idle idle1 idle2 idle3*2 idle4@400
+//! sequence tap tap1 tap2
+//!
+//! # every sequence is a state of the animation, see `animation::Trigger` for the triggers
+//! initial idle
+//! transition idle tap wpm>30
+//! transition tap idle idle:1000
+//!
+//! output current CURRENT_FRAME
+//! output base BASE_FRAME
+//! output prefix BASE_TO_
+//! ```
+//!
+//! The paths of the frames are relative to the manifest file.
+
+use crate::animation::{Animation, Transition};
+use crate::emit::{CEmitter, Emitter};
+use crate::generator::{check_c_identifier, encode, merge_frames, BaseStrategy, Encoded, Merge};
+use crate::scale::parse_dimensions;
+use crate::{Filter, Fit, Frame, Rotation};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    pub width: usize,
+    pub height: usize,
+    /// the default duration of a frame in milliseconds
+    pub duration: u32,
+    pub base: BaseStrategy,
+    /// the maximum number of pixels differing between a frame and a previous frame to replace it
+    /// by this frame, see `merge_frames`
+    pub tolerance: usize,
+    /// the dimensions of the panel and how the frames are adapted to it, if it's not the size
+    /// of the frames
+    pub panel: Option<(usize, usize, Fit)>,
+    /// the rotation of the QMK frame buffer
+    pub rotation: Rotation,
+    /// the name of the frames associated with the file they are read from
+    pub frames: Vec<(String, PathBuf)>,
+    /// the aliases associated with the frame they point to
+    pub aliases: Vec<(String, String)>,
+    pub sequences: Vec<Sequence>,
+    /// the sequence the animation starts with
+    pub initial: Option<String>,
+    pub transitions: Vec<Transition>,
+    pub output: OutputNames,
+}
+
+/// a named list of frames played one after the other
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    pub name: String,
+    /// the repeats are already expanded
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    /// the name of a frame or an alias
+    pub frame: String,
+    /// how long the frame is displayed in milliseconds
+    pub duration: u32,
+}
+
+/// the names used in the generated code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputNames {
+    /// the buffer the frames are decoded in
+    pub current: String,
+    /// the base frame
+    pub base: String,
+    /// prepended to the uppercased name of every frame
+    pub prefix: String,
+}
+
+impl Default for OutputNames {
+    fn default() -> Self {
+        Self {
+            current: "CURRENT_FRAME".to_string(),
+            base: "BASE_FRAME".to_string(),
+            prefix: "BASE_TO_".to_string(),
+        }
+    }
+}
+
+impl Manifest {
+    /// read a manifest file
+    pub fn load(filename: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(filename)?;
+        let dir = Path::new(filename)
+            .parent()
+            .unwrap_or_else(|| Path::new(""));
+        Self::parse(&content, dir).map_err(|e| format!("{}: {}", filename, e).into())
+    }
+
+    /// parse the content of a manifest, the paths of the frames are relative to `dir`
+    pub fn parse(content: &str, dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut size = None;
+        let mut duration = 100;
+        let mut rotation = Rotation::Deg0;
+        let mut panel = None;
+        let mut base = BaseStrategy::Suboptimal;
+        let mut tolerance = 0;
+        let mut frames = Vec::new();
+        let mut aliases = Vec::new();
+        // the sequences are resolved at the end since the default duration can be set anywhere
+        let mut sequences = Vec::new();
+        let mut initial = None;
+        let mut transitions = Vec::new();
+        let mut output = OutputNames::default();
+
+        for (line_nb, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let error = |msg: String| format!("line {}: {}", line_nb + 1, msg);
+
+            match words.as_slice() {
+                [] => (),
+                ["size", dimensions] => {
+                    let (width, height) = parse_dimensions(dimensions).ok_or_else(|| {
+                        error(format!(
+                            "invalid size `{}`, expected `WIDTHxHEIGHT`",
+                            dimensions
+                        ))
+                    })?;
+                    Frame::check_dimensions(width, height).map_err(|e| error(format!("{}", e)))?;
+                    size = Some((width, height))
+                }
+                ["panel", dimensions, fit] => {
+                    let (width, height) = parse_dimensions(dimensions).ok_or_else(|| {
+                        error(format!(
+                            "invalid panel size `{}`, expected `WIDTHxHEIGHT`",
+                            dimensions
+                        ))
+                    })?;
+                    Frame::check_dimensions(width, height).map_err(|e| error(format!("{}", e)))?;
+                    panel = Some((
+                        width,
+                        height,
+                        fit.parse().map_err(|e| error(format!("{}", e)))?,
+                    ))
+                }
+                ["duration", ms] => {
+                    duration = parse_duration(ms)
+                        .ok_or_else(|| error(format!("invalid duration `{}`", ms)))?
+                }
+                ["rotation", degrees] => {
+                    rotation = degrees.parse().map_err(|e| error(format!("{}", e)))?
+                }
+                ["base", strategy] => {
+                    base = strategy.parse().map_err(|e| error(format!("{}", e)))?
+                }
+                ["tolerance", pixels] => {
+                    tolerance = pixels
+                        .parse()
+                        .map_err(|_| error(format!("invalid tolerance `{}`", pixels)))?
+                }
+                ["frame", name, _] | ["alias", name, _]
+                    if frames.iter().any(|(frame, _)| frame == name)
+                        || aliases.iter().any(|(alias, _)| alias == name) =>
+                {
+                    return Err(error(format!("the frame `{}` is already defined", name)).into())
+                }
+                ["frame", name, path] => frames.push((name.to_string(), dir.join(path))),
+                ["alias", name, target] => aliases.push((name.to_string(), target.to_string())),
+                ["sequence", name, _, ..]
+                    if sequences.iter().any(|(_, sequence, _)| sequence == name) =>
+                {
+                    return Err(error(format!("the sequence `{}` is already defined", name)).into())
+                }
+                ["sequence", name, steps @ ..] if !steps.is_empty() => sequences.push((
+                    line_nb + 1,
+                    name.to_string(),
+                    steps.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+                )),
+                ["initial", name] => initial = Some(name.to_string()),
+                ["transition", from, to, trigger] => transitions.push(Transition {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    trigger: trigger.parse().map_err(|e| error(format!("{}", e)))?,
+                }),
+                ["output", "current", name] => output.current = name.to_string(),
+                ["output", "base", name] => output.base = name.to_string(),
+                ["output", "prefix", name] => output.prefix = name.to_string(),
+                _ => return Err(error(format!("can't understand `{}`", line)).into()),
+            }
+        }
+
+        let (width, height) = size.ok_or("the size of the frames was never specified")?;
+
+        let mut manifest = Self {
+            width,
+            height,
+            duration,
+            base,
+            tolerance,
+            panel,
+            rotation,
+            frames,
+            aliases,
+            sequences: Vec::new(),
+            initial,
+            transitions,
+            output,
+        };
+
+        for (line_nb, name, steps) in sequences {
+            let mut sequence = Sequence {
+                name,
+                steps: Vec::new(),
+            };
+            for step in steps {
+                let (frame, duration) = match step.split_once('@') {
+                    Some((frame, ms)) => (
+                        frame,
+                        parse_duration(ms).ok_or_else(|| {
+                            format!("line {}: invalid duration `{}`", line_nb, ms)
+                        })?,
+                    ),
+                    None => (step.as_str(), manifest.duration),
+                };
+                let (frame, repeat) = match frame.split_once('*') {
+                    // a frame repeated 0 times could leave an empty sequence
+                    Some((frame, repeat)) => (
+                        frame,
+                        repeat
+                            .parse()
+                            .ok()
+                            .filter(|&repeat| repeat > 0)
+                            .ok_or_else(|| {
+                                format!("line {}: invalid repeat `{}`", line_nb, repeat)
+                            })?,
+                    ),
+                    None => (frame, 1),
+                };
+                if manifest.resolve(frame).is_none() {
+                    return Err(format!("line {}: unknown frame `{}`", line_nb, frame).into());
+                }
+                for _ in 0..repeat {
+                    sequence.steps.push(Step {
+                        frame: frame.to_string(),
+                        duration,
+                    });
+                }
+            }
+            manifest.sequences.push(sequence);
+        }
+
+        // the names end up in the generated C
+        for name in manifest
+            .frames
+            .iter()
+            .map(|(frame, _)| frame)
+            .chain(manifest.aliases.iter().map(|(alias, _)| alias))
+        {
+            check_c_identifier(&manifest.array_name(name))
+                .map_err(|e| format!("the frame `{}` can't be used in C: {}", name, e))?;
+        }
+        for sequence in &manifest.sequences {
+            check_c_identifier(&sequence.name)?;
+        }
+        check_c_identifier(&manifest.output.current)?;
+        check_c_identifier(&manifest.output.base)?;
+
+        for (alias, _) in &manifest.aliases {
+            if manifest.resolve(alias).is_none() {
+                return Err(format!("the alias `{}` does not point to a frame", alias).into());
+            }
+        }
+        if !manifest.transitions.is_empty() || manifest.initial.is_some() {
+            // check that all the states exist
+            Animation::from_manifest(&manifest)?;
+        }
+        if let BaseStrategy::Frame(name) = &manifest.base {
+            if !manifest.frames.iter().any(|(frame, _)| frame == name) {
+                return Err(format!("the base frame `{}` does not exist", name).into());
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// follow the aliases until a real frame is found and return its name
+    pub fn resolve<'a>(&'a self, mut name: &'a str) -> Option<&'a str> {
+        // an alias can't go through more than all the aliases, otherwise there is a cycle
+        for _ in 0..=self.aliases.len() {
+            if self.frames.iter().any(|(frame, _)| frame == name) {
+                return Some(name);
+            }
+            name = &self.aliases.iter().find(|(alias, _)| alias == name)?.1;
+        }
+        None
+    }
+
+    /// read all the frames of the manifest
+    pub fn load_frames(&self) -> Result<Vec<(String, Frame)>, Box<dyn std::error::Error>> {
+        self.frames
+            .iter()
+            .map(|(name, path)| {
+                let path = path.to_str().ok_or("invalid path")?;
+                let frame = Frame::create_from_file(path)
+                    .map_err(|e| format!("can't read frame `{}` ({}): {}", name, path, e))?;
+                if frame.dimensions() != (self.width, self.height) {
+                    return Err(format!(
+                        "frame `{}` is {} × {} instead of {} × {}",
+                        name,
+                        frame.width(),
+                        frame.height(),
+                        self.width,
+                        self.height
+                    )
+                    .into());
+                }
+                Ok((name.clone(), frame))
+            })
+            .collect()
+    }
+
+    /// read all the frames of the manifest adapted to the panel, but not rotated, the frames
+    /// within the tolerance of a previous frame are replaced by it
+    pub fn load_panel_frames(&self) -> Result<Vec<(String, Frame)>, Box<dyn std::error::Error>> {
+        let mut frames = self.load_fitted_frames()?;
+        merge_frames(&mut frames, self.tolerance);
+        Ok(frames)
+    }
+
+    /// the frames replaced by a previous frame because they are within the tolerance
+    pub fn merges(&self) -> Result<Vec<Merge>, Box<dyn std::error::Error>> {
+        Ok(merge_frames(
+            &mut self.load_fitted_frames()?,
+            self.tolerance,
+        ))
+    }
+
+    fn load_fitted_frames(&self) -> Result<Vec<(String, Frame)>, Box<dyn std::error::Error>> {
+        Ok(self
+            .load_frames()?
+            .into_iter()
+            .map(|(name, frame)| match self.panel {
+                Some((width, height, fit)) => {
+                    (name, frame.fit_to_panel(width, height, fit, Filter::Area))
+                }
+                None => (name, frame),
+            })
+            .collect())
+    }
+
+    /// read all the frames of the manifest adapted to the panel and rotated for the frame buffer
+    pub fn load_buffer_frames(&self) -> Result<Vec<(String, Frame)>, Box<dyn std::error::Error>> {
+        Ok(self
+            .load_panel_frames()?
+            .into_iter()
+            .map(|(name, frame)| (name, frame.rotate(self.rotation.inverse())))
+            .collect())
+    }
+
+    /// same as `load_buffer_frames` without replacing the frames within the tolerance of a
+    /// previous frame
+    pub fn load_unmerged_buffer_frames(
+        &self,
+    ) -> Result<Vec<(String, Frame)>, Box<dyn std::error::Error>> {
+        Ok(self
+            .load_fitted_frames()?
+            .into_iter()
+            .map(|(name, frame)| (name, frame.rotate(self.rotation.inverse())))
+            .collect())
+    }
+
+    /// load and encode all the frames of the manifest, see `load_buffer_frames`
+    pub fn encode(&self) -> Result<Encoded, Box<dyn std::error::Error>> {
+        encode(&self.load_buffer_frames()?, &self.base)
+    }
+
+    /// the name of the array of a frame in the generated code
+    pub fn array_name(&self, frame: &str) -> String {
+        format!("{}{}", self.output.prefix, frame.to_uppercase())
+    }
+
+    /// the C arrays of the frames, the identical frames being aliases of the first one, the
+    /// aliases and the tables of the sequences, see `generate_c`
+    pub fn arrays_c(&self, encoded: &Encoded) -> String {
+        self.arrays_c_with(encoded, "PROGMEM")
+    }
+
+    /// same as `arrays_c` with another qualifier than `PROGMEM` for the arrays of the frames, see
+    /// `emit::CEmitter`
+    pub fn arrays_c_with(&self, encoded: &Encoded, qualifier: &str) -> String {
+        let mut emitter = CEmitter {
+            qualifier: qualifier.to_string(),
+            ..CEmitter::default()
+        };
+        let mut out = Vec::new();
+        encoded
+            .emit(
+                &mut out,
+                &mut emitter,
+                &self.output.base,
+                &self.output.prefix,
+            )
+            .and_then(|_| {
+                self.aliases.iter().try_for_each(|(alias, target)| {
+                    let len = self
+                        .resolve(target)
+                        .and_then(|frame| encoded.frames.iter().find(|(name, _)| name == frame))
+                        .map_or(0, |(_, data)| data.len());
+                    emitter.alias(
+                        &mut out,
+                        &self.array_name(alias),
+                        &self.array_name(target),
+                        len,
+                    )
+                })
+            })
+            .expect("writing to a vec can't fail");
+        let mut c = String::from_utf8_lossy(&out).into_owned();
+
+        for sequence in &self.sequences {
+            let name = sequence.name.to_uppercase();
+            let len = sequence.steps.len();
+            let arrays: Vec<String> = sequence
+                .steps
+                .iter()
+                .map(|step| self.array_name(&step.frame))
+                .collect();
+
+            c.push_str(&format!("#define {}_LENGTH {}\n", name, len));
+            c.push_str(&format!(
+                "static const uint8_t *const {}_SEQUENCE[{}] = {{ {} }};\n",
+                name,
+                len,
+                arrays.join(", ")
+            ));
+            c.push_str(&format!(
+                "static const uint16_t {}_SIZES[{}] = {{ {} }};\n",
+                name,
+                len,
+                arrays
+                    .iter()
+                    .map(|array| format!("sizeof({})", array))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+            c.push_str(&format!(
+                "static const uint16_t {}_DURATIONS[{}] = {{ {} }};\n",
+                name,
+                len,
+                sequence
+                    .steps
+                    .iter()
+                    .map(|step| step.duration.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        c
+    }
+
+    /// write the C code of the whole animation to `out`
+    pub fn generate_c(&self, out: &mut dyn Write) -> Result<(), Box<dyn std::error::Error>> {
+        let encoded = self.encode()?;
+
+        writeln!(
+            out,
+            "static char {}[{}] = {{ 0 }};",
+            self.output.current, encoded.buffer_len
+        )?;
+        write!(out, "{}", self.arrays_c(&encoded))?;
+        if !self.transitions.is_empty() {
+            write!(out, "{}", Animation::from_manifest(self)?.generate_c())?;
+        }
+
+        writeln!(out, "// total array size is {} bytes", encoded.total_size())?;
+        Ok(())
+    }
+}
+
+/// parse the duration of a frame in milliseconds, the durations are stored in `uint16_t` so they
+/// can't be longer than 65535ms
+fn parse_duration(ms: &str) -> Option<u32> {
+    ms.parse::<u16>().ok().map(u32::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::Trigger;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = Manifest::parse(
+            "
+            # a comment
+            size 128x40
+            duration 200
+            base frame:b
+
+            frame a dir/a
+            frame b dir/b # another comment
+            alias c a
+            sequence idle a*2 c@300 b*2@50
+            sequence tap b
+            transition idle tap keypress
+            output prefix ANIM_
+            ",
+            Path::new("/frames"),
+        )
+        .unwrap();
+
+        assert_eq!((manifest.width, manifest.height), (128, 40));
+        assert_eq!(manifest.base, BaseStrategy::Frame("b".to_string()));
+        assert_eq!(
+            manifest.frames[1],
+            ("b".to_string(), PathBuf::from("/frames/dir/b"))
+        );
+        assert_eq!(manifest.resolve("c"), Some("a"));
+        assert_eq!(manifest.array_name("c"), "ANIM_C");
+        assert_eq!(manifest.output.current, "CURRENT_FRAME");
+        assert_eq!(manifest.initial, None);
+        assert_eq!(manifest.transitions[0].trigger, Trigger::KeyPress);
+
+        let steps: Vec<(&str, u32)> = manifest.sequences[0]
+            .steps
+            .iter()
+            .map(|step| (step.frame.as_str(), step.duration))
+            .collect();
+        assert_eq!(
+            steps,
+            [("a", 200), ("a", 200), ("c", 300), ("b", 50), ("b", 50)]
+        );
+    }
+
+    #[test]
+    fn test_invalid_manifest() {
+        let dir = Path::new("");
+        assert!(Manifest::parse("frame a a", dir).is_err());
+        assert!(Manifest::parse("size 12", dir).is_err());
+        // the frames are stored in pages of 8 rows
+        assert!(Manifest::parse("size 8x12", dir).is_err());
+        assert!(Manifest::parse("size 0x8", dir).is_err());
+        assert!(Manifest::parse("size 8x8\nframe a a\nsequence s b", dir).is_err());
+        assert!(Manifest::parse("size 8x8\nframe a a\nalias b c\nalias c b", dir).is_err());
+        assert!(Manifest::parse("size 8x8\nbase frame:z", dir).is_err());
+        assert!(Manifest::parse("size 8x8\nunknown", dir).is_err());
+        // the names are used in the generated C
+        assert!(Manifest::parse("size 8x8\nframe frame-01 a", dir).is_err());
+        assert!(Manifest::parse("size 8x8\nframe a a\nalias a.b a", dir).is_err());
+        assert!(Manifest::parse("size 8x8\nframe 01 a\noutput prefix X", dir).is_ok());
+        assert!(Manifest::parse("size 8x8\nframe a a\nsequence my-idle a", dir).is_err());
+        // the durations are stored in uint16_t
+        assert!(Manifest::parse("size 8x8\nduration 70000", dir).is_err());
+        assert!(Manifest::parse("size 8x8\nframe a a\nsequence s a@65536", dir).is_err());
+        assert!(Manifest::parse("size 8x8\nframe a a\nsequence s a@65535", dir).is_ok());
+        assert!(Manifest::parse("size 8x8\nframe a a\nsequence s a\ninitial b", dir).is_err());
+        // the names are defined once and the sequences are not empty
+        assert!(Manifest::parse("size 8x8\nframe a a\nframe a b", dir).is_err());
+        assert!(Manifest::parse("size 8x8\nframe a a\nalias a a", dir).is_err());
+        assert!(Manifest::parse("size 8x8\nframe a a\nsequence s a\nsequence s a", dir).is_err());
+        assert!(Manifest::parse("size 8x8\nframe a a\nsequence s a*0", dir).is_err());
+        assert!(
+            Manifest::parse("size 8x8\nframe a a\nsequence s a\ntransition s b end", dir).is_err()
+        );
+    }
+
+    #[test]
+    fn test_load_bongo_cat() {
+        let path = format!("{}/bongo_cat.manifest", env!("CARGO_MANIFEST_DIR"));
+        let manifest = Manifest::load(&path).unwrap();
+        let frames = manifest.load_frames().unwrap();
+        assert_eq!(frames.len(), 7);
+        assert!(manifest.encode().unwrap().total_size() < 640 * frames.len());
+
+        let mut c = Vec::new();
+        manifest.generate_c(&mut c).unwrap();
+        let c = String::from_utf8(c).unwrap();
+        assert!(c.starts_with("static char CURRENT_FRAME[640] = { 0 };\n"));
+        assert!(c.ends_with(" bytes\n"));
+    }
+
+    #[test]
+    fn test_rotated_manifest() {
+        let content = "size 128x40\nrotation 90\nbase none\nframe idle1 bongo_cat_frames/idle1";
+        let manifest = Manifest::parse(content, Path::new(env!("CARGO_MANIFEST_DIR"))).unwrap();
+        assert_eq!(manifest.rotation, Rotation::Deg90);
+
+        let (_, idle1) = &manifest.load_frames().unwrap()[0];
+        let encoded = manifest.encode().unwrap();
+        assert_eq!(
+            crate::uncompress(&encoded.frames[0].1),
+            idle1.output_rotated(Rotation::Deg90)
+        );
+        assert!(Manifest::parse("size 8x8\nrotation 45", Path::new("")).is_err());
+    }
+
+    #[test]
+    fn test_tolerance_manifest() {
+        let content = "size 128x40\ntolerance 0\nbase none\n\
+                       frame idle1 bongo_cat_frames/idle1\n\
+                       frame idle2 bongo_cat_frames/idle1\n\
+                       frame tap1 bongo_cat_frames/tap1";
+        let manifest = Manifest::parse(content, Path::new(env!("CARGO_MANIFEST_DIR"))).unwrap();
+        assert_eq!(manifest.tolerance, 0);
+        let merges = manifest.merges().unwrap();
+        assert_eq!(merges.len(), 1);
+        assert_eq!(
+            (merges[0].frame.as_str(), merges[0].into.as_str()),
+            ("idle2", "idle1")
+        );
+        assert_eq!(
+            manifest.encode().unwrap().duplicates(),
+            vec![("idle2".to_string(), "idle1".to_string())]
+        );
+
+        let manifest = Manifest::parse(
+            &content.replace("tolerance 0", "tolerance 5000"),
+            Path::new(env!("CARGO_MANIFEST_DIR")),
+        )
+        .unwrap();
+        assert_eq!(manifest.merges().unwrap().len(), 2);
+        assert!(Manifest::parse("size 8x8\ntolerance few", Path::new("")).is_err());
+    }
+
+    #[test]
+    fn test_panel_manifest() {
+        let content = "size 128x40\npanel 64x48 fit\nbase none\nframe idle1 bongo_cat_frames/idle1";
+        let manifest = Manifest::parse(content, Path::new(env!("CARGO_MANIFEST_DIR"))).unwrap();
+        assert_eq!(manifest.panel, Some((64, 48, Fit::Fit)));
+        assert_eq!(manifest.encode().unwrap().buffer_len, 64 * 48 / 8);
+        assert!(Manifest::parse("size 8x8\npanel 8x8 stretch", Path::new("")).is_err());
+        assert!(Manifest::parse("size 8x8\npanel 8 fit", Path::new("")).is_err());
+        assert!(Manifest::parse("size 8x8\npanel 100x20 fit", Path::new("")).is_err());
+    }
+}
@@ -0,0 +1,475 @@
+use crate::Frame;
+
+/// an array of bytes extracted from a C or rust source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedArray {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+impl ParsedArray {
+    /// return the raw bytes of the frame.
+    /// If `compressed` is set the array is first given to `uncompress`, then if a `base` is
+    /// provided the result is undiffed against it, exactly like the firmware would do.
+    /// Fails if the compressed array is truncated
+    pub fn decode(
+        &self,
+        compressed: bool,
+        base: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut data = if compressed {
+            crate::try_uncompress(&self.data)
+                .map_err(|error| format!("array `{}` can't be decoded: {}", self.name, error))?
+        } else {
+            self.data.clone()
+        };
+
+        if let Some(base) = base {
+            // a trimmed diff is only missing zeros, and a zero in a diff means "same as the base"
+            if data.len() < base.len() {
+                data.resize(base.len(), 0);
+            }
+            crate::undiff(base, &mut data);
+        }
+        Ok(data)
+    }
+
+    /// convert the array to a `Frame`, the array must contain raw (uncompressed) bytes
+    pub fn to_frame(
+        &self,
+        width: usize,
+        height: usize,
+    ) -> Result<Frame, Box<dyn std::error::Error>> {
+        bytes_to_frame(&self.name, &self.data, width, height)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Number(u64),
+    Punct(char),
+}
+
+/// find all the `uint8_t` (or `unsigned char`) C arrays and `[u8; N]` rust arrays in a source
+/// file.
+///
+/// The values can be written in decimal, hexadecimal (`0x`) or binary (`0b`).
+/// Aliases to a previous array are also recognized, in C with `#define ALIAS NAME` and in rust
+/// with `const ALIAS: [u8; N] = NAME;`. The values can also be constants previously defined with
+/// `#define NAME VALUE`.
+pub fn parse_arrays(source: &str) -> Result<Vec<ParsedArray>, Box<dyn std::error::Error>> {
+    let tokens = tokenize(source)?;
+    let mut arrays: Vec<ParsedArray> = Vec::new();
+    let mut defines: Vec<(String, u64)> = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            // C array: uint8_t [qualifiers] NAME[N] [qualifiers] = { ... };
+            Token::Ident(ident)
+                if ident == "uint8_t"
+                    || (ident == "unsigned"
+                        && tokens.get(i + 1) == Some(&Token::Ident("char".to_string()))) =>
+            {
+                let mut name = None;
+                i += 1;
+                while let Some(Token::Ident(ident)) = tokens.get(i) {
+                    name = Some(ident.clone());
+                    i += 1;
+                }
+                let name = match (name, tokens.get(i)) {
+                    (Some(name), Some(Token::Punct('['))) => name,
+                    // this is a pointer or a simple variable, not an array
+                    _ => continue,
+                };
+                while !matches!(tokens.get(i), Some(Token::Punct(']')) | None) {
+                    i += 1;
+                }
+                i += 1;
+                // the qmk fonts are declared as `font[] PROGMEM`
+                while let Some(Token::Ident(_)) = tokens.get(i) {
+                    i += 1;
+                }
+                if tokens.get(i) != Some(&Token::Punct('='))
+                    || tokens.get(i + 1) != Some(&Token::Punct('{'))
+                {
+                    // only a declaration
+                    continue;
+                }
+                let (data, next) = parse_values(&tokens, i + 2, '}', &name, &defines)?;
+                arrays.push(ParsedArray { name, data });
+                i = next;
+            }
+            // rust array: NAME: [u8; N] = [ ... ]; or NAME: [u8; N] = OTHER;
+            Token::Ident(name)
+                if tokens.get(i + 1) == Some(&Token::Punct(':'))
+                    && tokens.get(i + 2) == Some(&Token::Punct('['))
+                    && tokens.get(i + 3) == Some(&Token::Ident("u8".to_string()))
+                    && tokens.get(i + 4) == Some(&Token::Punct(';')) =>
+            {
+                let name = name.clone();
+                i += 5;
+                while !matches!(tokens.get(i), Some(Token::Punct(']')) | None) {
+                    i += 1;
+                }
+                i += 1;
+                if tokens.get(i) != Some(&Token::Punct('=')) {
+                    continue;
+                }
+                match tokens.get(i + 1) {
+                    Some(Token::Punct('[')) => {
+                        let (data, next) = parse_values(&tokens, i + 2, ']', &name, &defines)?;
+                        arrays.push(ParsedArray { name, data });
+                        i = next;
+                    }
+                    Some(Token::Ident(other)) => {
+                        if let Some(data) = find_array(&arrays, other) {
+                            arrays.push(ParsedArray { name, data });
+                        }
+                        i += 2;
+                    }
+                    _ => i += 1,
+                }
+            }
+            // C alias: #define ALIAS NAME
+            Token::Punct('#') => match (tokens.get(i + 1), tokens.get(i + 2), tokens.get(i + 3)) {
+                (
+                    Some(Token::Ident(define)),
+                    Some(Token::Ident(alias)),
+                    Some(Token::Ident(other)),
+                ) if define == "define" => {
+                    if let Some(data) = find_array(&arrays, other) {
+                        arrays.push(ParsedArray {
+                            name: alias.clone(),
+                            data,
+                        });
+                    }
+                    i += 4;
+                }
+                // C constant: #define NAME VALUE
+                (
+                    Some(Token::Ident(define)),
+                    Some(Token::Ident(name)),
+                    Some(Token::Number(value)),
+                ) if define == "define" => {
+                    defines.push((name.clone(), *value));
+                    i += 4;
+                }
+                _ => i += 1,
+            },
+            _ => i += 1,
+        }
+    }
+
+    Ok(arrays)
+}
+
+/// parse all the arrays of a source file and convert them to frames.
+///
+/// When `compressed` is set every array is decoded with `uncompress` first. If `base` names one
+/// of the arrays of the file this array is kept raw and used to undiff all the others.
+pub fn parse_frames(
+    source: &str,
+    width: usize,
+    height: usize,
+    compressed: bool,
+    base: Option<&str>,
+) -> Result<Vec<(String, Frame)>, Box<dyn std::error::Error>> {
+    Frame::check_dimensions(width, height)?;
+    let arrays = parse_arrays(source)?;
+    let base_data = match base {
+        Some(base) => Some(
+            find_array(&arrays, base)
+                .ok_or_else(|| format!("base array `{}` not found in the source", base))?,
+        ),
+        None => None,
+    };
+
+    arrays
+        .iter()
+        .map(|array| {
+            let data = if Some(array.name.as_str()) == base {
+                array.data.clone()
+            } else {
+                array.decode(compressed, base_data.as_deref())?
+            };
+            Ok((
+                array.name.clone(),
+                bytes_to_frame(&array.name, &data, width, height)?,
+            ))
+        })
+        .collect()
+}
+
+fn find_array(arrays: &[ParsedArray], name: &str) -> Option<Vec<u8>> {
+    arrays
+        .iter()
+        .rev()
+        .find(|array| array.name == name)
+        .map(|array| array.data.clone())
+}
+
+fn bytes_to_frame(
+    name: &str,
+    data: &[u8],
+    width: usize,
+    height: usize,
+) -> Result<Frame, Box<dyn std::error::Error>> {
+    Frame::check_dimensions(width, height)?;
+    if data.len() * 8 > width * height {
+        return Err(format!(
+            "array `{}` is too big ({} pixels) for a {} × {} frame",
+            name,
+            data.len() * 8,
+            width,
+            height
+        )
+        .into());
+    }
+    Frame::new(width, height, data)
+}
+
+/// parse a list of comma separated values starting at `i` until the `end` punctuation.
+/// Return the values and the index of the token following `end`
+fn parse_values(
+    tokens: &[Token],
+    mut i: usize,
+    end: char,
+    name: &str,
+    defines: &[(String, u64)],
+) -> Result<(Vec<u8>, usize), Box<dyn std::error::Error>> {
+    let mut data = Vec::new();
+
+    loop {
+        let token = match tokens.get(i) {
+            Some(Token::Ident(ident)) => match defines.iter().rev().find(|(name, _)| name == ident)
+            {
+                Some((_, value)) => Some(Token::Number(*value)),
+                None => tokens.get(i).cloned(),
+            },
+            token => token.cloned(),
+        };
+        match token.as_ref() {
+            Some(Token::Number(n)) if *n <= u8::MAX as u64 => data.push(*n as u8),
+            Some(Token::Number(n)) => {
+                return Err(
+                    format!("value {} in array `{}` does not fit in a byte", n, name).into(),
+                )
+            }
+            Some(Token::Punct(',')) => (),
+            Some(Token::Punct(c)) if *c == end => return Ok((data, i + 1)),
+            Some(token) => return Err(format!("unexpected {:?} in array `{}`", token, name).into()),
+            None => return Err(format!("array `{}` is never closed", name).into()),
+        }
+        i += 1;
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, Box<dyn std::error::Error>> {
+    let source = source.as_bytes();
+    let mut tokens = Vec::new();
+
+    let mut i = 0;
+    while i < source.len() {
+        let c = source[i];
+        match c {
+            b'/' if source.get(i + 1) == Some(&b'/') => {
+                while i < source.len() && source[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if source.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i < source.len() && !source[i..].starts_with(b"*/") {
+                    i += 1;
+                }
+                i += 2;
+            }
+            b'"' => {
+                // we don't care about strings but they could contains anything
+                i += 1;
+                while i < source.len() && source[i] != b'"' {
+                    if source[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'0'..=b'9' => {
+                let start = i;
+                while i < source.len() && (source[i].is_ascii_alphanumeric() || source[i] == b'_') {
+                    i += 1;
+                }
+                let literal = std::str::from_utf8(&source[start..i])?;
+                tokens.push(Token::Number(parse_number(literal)?));
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                let start = i;
+                while i < source.len() && (source[i].is_ascii_alphanumeric() || source[i] == b'_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(
+                    std::str::from_utf8(&source[start..i])?.to_string(),
+                ));
+            }
+            c if c.is_ascii_whitespace() => i += 1,
+            c => {
+                tokens.push(Token::Punct(c as char));
+                i += 1;
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// parse a C or rust integer literal like `42`, `0x2a`, `0b0010_1010` or `42u8`
+fn parse_number(literal: &str) -> Result<u64, Box<dyn std::error::Error>> {
+    let literal = literal.replace('_', "");
+    let lower = literal.to_lowercase();
+    let (digits, radix) = if let Some(hex) = lower.strip_prefix("0x") {
+        (hex, 16)
+    } else if let Some(bin) = lower.strip_prefix("0b") {
+        (bin, 2)
+    } else {
+        (lower.as_str(), 10)
+    };
+    // remove the type suffixes: `u8`, `U`, `UL`...
+    let digits = match digits.find(|c: char| !c.is_digit(radix)) {
+        Some(idx) => &digits[..idx],
+        None => digits,
+    };
+
+    u64::from_str_radix(digits, radix)
+        .map_err(|e| format!("invalid number `{}`: {}", literal, e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_c_array() {
+        let source = "
+            // a comment with uint8_t FAKE[2] = { 1, 2 };
+            static const uint8_t PROGMEM FRAME[4] = {
+                0, 0x12, 0XFF, 0b101,
+            };
+            /* another comment */
+            uint8_t OTHER[] = {3, 2u, 1};
+            #define ALIAS OTHER
+            const unsigned char font[] PROGMEM = { 0x3E, 0x5B };
+            unsigned int NOT_BYTES[] = { 1 };
+        ";
+        assert_eq!(
+            parse_arrays(source).unwrap(),
+            vec![
+                ParsedArray {
+                    name: "FRAME".to_string(),
+                    data: vec![0, 0x12, 0xff, 0b101]
+                },
+                ParsedArray {
+                    name: "OTHER".to_string(),
+                    data: vec![3, 2, 1]
+                },
+                ParsedArray {
+                    name: "ALIAS".to_string(),
+                    data: vec![3, 2, 1]
+                },
+                ParsedArray {
+                    name: "font".to_string(),
+                    data: vec![0x3e, 0x5b]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rust_array() {
+        let source = "
+            const FRAME: [u8; 3] = [0x01, 2_u8, 0b1000_0010 /* 130 */];
+            static ALIAS: [u8; 3] = FRAME;
+        ";
+        assert_eq!(
+            parse_arrays(source).unwrap(),
+            vec![
+                ParsedArray {
+                    name: "FRAME".to_string(),
+                    data: vec![1, 2, 130]
+                },
+                ParsedArray {
+                    name: "ALIAS".to_string(),
+                    data: vec![1, 2, 130]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_value() {
+        assert!(parse_arrays("uint8_t A[1] = { 256 };").is_err());
+        assert!(parse_arrays("uint8_t A[1] = { 2 ").is_err());
+        assert!(parse_arrays("uint8_t A[1] = { UNKNOWN };").is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_dimensions() {
+        let source = "uint8_t F1[1] = { 5 };";
+        assert!(parse_frames(source, 0, 8, true, None).is_err());
+        assert!(parse_frames(source, 8, 12, false, None).is_err());
+    }
+
+    #[test]
+    fn test_parse_truncated_frame() {
+        let error = parse_frames("uint8_t F1[1] = { 5 };", 2, 8, true, None).unwrap_err();
+        assert!(error.to_string().contains("`F1`"), "{}", error);
+    }
+
+    #[test]
+    fn test_parse_defined_values() {
+        let source = "#define IDLE_LENGTH 5\n#define TAP_LENGTH 0x2\n\
+                      static const uint8_t ANIMATION_LENGTHS[2] = { IDLE_LENGTH, TAP_LENGTH };";
+        assert_eq!(
+            parse_arrays(source).unwrap(),
+            vec![ParsedArray {
+                name: "ANIMATION_LENGTHS".to_string(),
+                data: vec![5, 2]
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_compressed_frames_from_base() {
+        // the sources of the old bongo cat binaries, with the frames written by hand
+        let raw = parse_arrays(include_str!("../tests/fixtures/bongo_cat.rs")).unwrap();
+        let idle1 = raw.iter().find(|array| array.name == "IDLE1").unwrap();
+        let idle1 = idle1.to_frame(128, 40).unwrap();
+
+        let frames = parse_frames(
+            include_str!("../tests/fixtures/bongo_cat_print.rs"),
+            128,
+            40,
+            true,
+            Some("BASE_FRAME"),
+        )
+        .unwrap();
+        let names: Vec<&str> = frames.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "BASE_FRAME",
+                "BASE_TO_IDLE1",
+                "BASE_TO_IDLE2",
+                "BASE_TO_IDLE3",
+                "BASE_TO_IDLE4",
+                "BASE_TO_IDLE5",
+                "BASE_TO_TAP1",
+                "BASE_TO_TAP2"
+            ]
+        );
+        assert_eq!(frames[1].1.output(), idle1.output());
+        assert_eq!(frames[2].1.output(), idle1.output());
+    }
+}
@@ -0,0 +1,138 @@
+//! generate a drop-in QMK module playing the animation of a manifest.
+//!
+//! The module contains the arrays, a decoder doing the same thing as `uncompress2` and `undiff`
+//! on the `PROGMEM` arrays, the state machine of `Animation::generate_c` and a render function
+//! writing the frame with `oled_write_raw` when it changes.
+
+use crate::animation::Animation;
+use crate::generator::{check_c_identifier, decoder_c, Module};
+use crate::manifest::Manifest;
+use crate::Rotation;
+
+/// generate the `<name>.h` and `<name>.c` QMK module of a manifest, `name` is also the prefix
+/// of the functions of the module
+pub fn generate_module(
+    manifest: &Manifest,
+    name: &str,
+) -> Result<Module, Box<dyn std::error::Error>> {
+    check_c_identifier(name)?;
+    let animation = Animation::from_manifest(manifest)?;
+    let encoded = manifest.encode()?;
+    let (width, height) = manifest
+        .panel
+        .map_or((manifest.width, manifest.height), |(width, height, _)| {
+            (width, height)
+        });
+    let upper = name.to_uppercase();
+    let current = &manifest.output.current;
+
+    let rotation = match manifest.rotation {
+        Rotation::Deg0 => "OLED_ROTATION_0",
+        Rotation::Deg90 => "OLED_ROTATION_90",
+        Rotation::Deg180 => "OLED_ROTATION_180",
+        Rotation::Deg270 => "OLED_ROTATION_270",
+    };
+
+    let header = format!(
+        "/* generated by kyria-oled, do not edit.
+ *
+ * A {width} x {height} animation for the QMK OLED driver, to use it:
+ * - add `SRC += {name}.c` to your `rules.mk`, and `WPM_ENABLE = yes` if the animation changes
+ *   with the WPM, without it the WPM is always 0
+ * - return `{upper}_ROTATION` from `oled_init_user`
+ * - call `{name}_key_pressed()` from `process_record_user` on every key press
+ * - call `{name}_render()` from `oled_task_user`, the OLED is only written when the frame
+ *   changes
+ */
+#pragma once
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#define {upper}_ROTATION {rotation}
+#define {upper}_WIDTH {width}
+#define {upper}_HEIGHT {height}
+
+void {name}_key_pressed(void);
+void {name}_render(void);
+",
+        width = width,
+        height = height,
+        name = name,
+        upper = upper,
+        rotation = rotation,
+    );
+
+    let mut source = format!(
+        "/* generated by kyria-oled, do not edit, see {name}.h */
+#include QMK_KEYBOARD_H
+#include \"{name}.h\"
+
+static char {current}[{len}] = {{ 0 }};
+",
+        name = name,
+        current = current,
+        len = encoded.buffer_len
+    );
+    source.push_str(&manifest.arrays_c(&encoded));
+    source.push_str(&animation.generate_c());
+    source.push_str(&format!(
+        "{decoder}
+void {name}_key_pressed(void) {{
+    animation_key_pressed();
+}}
+
+void {name}_render(void) {{
+    static bool drawn = false;
+#ifdef WPM_ENABLE
+    uint8_t wpm = get_current_wpm();
+#else
+    uint8_t wpm = 0;
+#endif
+    if (animation_update(wpm) || !drawn) {{
+        drawn = true;
+        {name}_decode(animation_frame(), animation_frame_size());
+        oled_write_raw({current}, sizeof({current}));
+    }}
+}}
+",
+        name = name,
+        current = current,
+        decoder = decoder_c(
+            &format!("{}_decode", name),
+            current,
+            encoded.base.as_ref().map(|_| manifest.output.base.as_str()),
+            true
+        ),
+    ));
+
+    Ok(Module {
+        name: name.to_string(),
+        header,
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_module() {
+        let path = format!("{}/bongo_cat.manifest", env!("CARGO_MANIFEST_DIR"));
+        let manifest = Manifest::load(&path).unwrap();
+        let module = generate_module(&manifest, "bongo_cat").unwrap();
+
+        assert_eq!(module.name, "bongo_cat");
+        assert!(module
+            .header
+            .contains("#define BONGO_CAT_ROTATION OLED_ROTATION_0"));
+        assert!(module.header.contains("void bongo_cat_render(void);"));
+        assert!(module.source.contains("#include \"bongo_cat.h\""));
+        assert!(module.source.contains("static char CURRENT_FRAME[640]"));
+        assert!(module.source.contains("pgm_read_byte(BASE_FRAME + i)"));
+        assert!(module.source.contains("bool animation_update(uint8_t wpm)"));
+
+        assert!(generate_module(&manifest, "bongo-cat").is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! resize the frames, and fit them to the panels of other sizes.
+
+use crate::{BlendMode, Frame};
+
+/// how the pixels are computed when a frame is resized
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    /// every pixel takes the value of the nearest source pixel
+    Nearest,
+    /// every pixel is on if at least half of the area it covers in the source frame is on,
+    /// it keeps the thin lines better when downscaling
+    Area,
+}
+
+/// how a frame is adapted to a panel of another size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fit {
+    /// scale the frame as much as possible while keeping it entirely visible, the remaining
+    /// space is black
+    Fit,
+    /// scale the frame to cover the whole panel and crop what goes outside
+    Crop,
+    /// center the frame on the panel without scaling it
+    Letterbox,
+}
+
+impl std::str::FromStr for Fit {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fit" => Ok(Fit::Fit),
+            "crop" => Ok(Fit::Crop),
+            "letterbox" => Ok(Fit::Letterbox),
+            s => Err(format!("invalid fit `{}`, expected `fit`, `crop` or `letterbox`", s).into()),
+        }
+    }
+}
+
+/// parse dimensions written like `128x32`
+pub fn parse_dimensions(s: &str) -> Option<(usize, usize)> {
+    let (width, height) = s.split_once('x')?;
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
+
+impl Frame {
+    /// resize the frame to the specified dimensions, the aspect ratio is not preserved
+    pub fn scale(&self, width: usize, height: usize, filter: Filter) -> Self {
+        let (src_width, src_height) = self.dimensions();
+        let mut frame = Frame::empty(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let on = match filter {
+                    Filter::Nearest => self
+                        .get(x * src_width / width, y * src_height / height)
+                        .unwrap(),
+                    Filter::Area => {
+                        // we work in units of 1 / width (or 1 / height) of a source pixel so
+                        // every boundary is an integer
+                        let (left, right) = (x * src_width, (x + 1) * src_width);
+                        let (top, bottom) = (y * src_height, (y + 1) * src_height);
+                        let mut covered = 0;
+                        for sy in top / height..bottom.div_ceil(height) {
+                            let h = bottom.min((sy + 1) * height) - top.max(sy * height);
+                            for sx in left / width..right.div_ceil(width) {
+                                if self.get(sx, sy).unwrap() {
+                                    covered +=
+                                        h * (right.min((sx + 1) * width) - left.max(sx * width));
+                                }
+                            }
+                        }
+                        covered * 2 >= src_width * src_height
+                    }
+                };
+                frame.set(x, y, on);
+            }
+        }
+
+        frame
+    }
+
+    /// double the size of the frame with the scale2x (also known as EPX) algorithm, it keeps
+    /// the diagonals of the pixel art smooth
+    pub fn scale2x(&self) -> Self {
+        let (width, height) = self.dimensions();
+        let mut frame = Frame::empty(width * 2, height * 2);
+        // outside of the frame we use the center pixel
+        let pixel = |x: usize, y: usize, dx: isize, dy: isize| {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            if nx < 0 || ny < 0 {
+                self.get(x, y).unwrap()
+            } else {
+                self.get(nx as usize, ny as usize)
+                    .unwrap_or_else(|| self.get(x, y).unwrap())
+            }
+        };
+
+        for y in 0..height {
+            for x in 0..width {
+                let p = pixel(x, y, 0, 0);
+                let a = pixel(x, y, 0, -1);
+                let b = pixel(x, y, 1, 0);
+                let c = pixel(x, y, -1, 0);
+                let d = pixel(x, y, 0, 1);
+
+                let top_left = if c == a && c != d && a != b { a } else { p };
+                let top_right = if a == b && a != c && b != d { b } else { p };
+                let bottom_left = if d == c && d != b && c != a { c } else { p };
+                let bottom_right = if b == d && b != a && d != c { d } else { p };
+
+                frame.set(x * 2, y * 2, top_left);
+                frame.set(x * 2 + 1, y * 2, top_right);
+                frame.set(x * 2, y * 2 + 1, bottom_left);
+                frame.set(x * 2 + 1, y * 2 + 1, bottom_right);
+            }
+        }
+
+        frame
+    }
+
+    /// adapt the frame to a panel of the specified dimensions, the frame is always centered
+    pub fn fit_to_panel(&self, width: usize, height: usize, fit: Fit, filter: Filter) -> Self {
+        let (src_width, src_height) = self.dimensions();
+        let scaled = match fit {
+            Fit::Letterbox => self.clone(),
+            Fit::Fit | Fit::Crop => {
+                // compare width / src_width and height / src_height without rounding
+                let width_limited = width * src_height <= height * src_width;
+                let (scaled_width, scaled_height) = if width_limited == (fit == Fit::Fit) {
+                    (width, src_height * width / src_width)
+                } else {
+                    (src_width * height / src_height, height)
+                };
+                // a very wide or very tall frame must keep at least a pixel
+                self.scale(scaled_width.max(1), scaled_height.max(1), filter)
+            }
+        };
+
+        let mut frame = Frame::empty(width, height);
+        frame.blit(
+            &scaled,
+            (width as isize - scaled.width() as isize) / 2,
+            (height as isize - scaled.height() as isize) / 2,
+            BlendMode::Copy,
+        );
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(drawing: &str) -> Frame {
+        let lines: Vec<&str> = drawing.split_whitespace().collect();
+        let mut frame = Frame::empty(lines[0].len(), lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            for (x, b) in line.bytes().enumerate() {
+                frame.set(x, y, b == b'#');
+            }
+        }
+        frame
+    }
+
+    #[test]
+    fn test_parse_dimensions() {
+        assert_eq!(parse_dimensions("128x32"), Some((128, 32)));
+        assert_eq!(parse_dimensions("128"), None);
+        assert_eq!(parse_dimensions("128x"), None);
+        assert_eq!(parse_dimensions("-1x32"), None);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let f = frame("#. .#");
+        assert_eq!(f.scale(4, 4, Filter::Nearest), frame("##.. ##.. ..## ..##"));
+        assert_eq!(f.scale(1, 1, Filter::Nearest), frame("#"));
+        assert_eq!(f.scale(3, 2, Filter::Nearest), frame("##. ..#"));
+    }
+
+    #[test]
+    fn test_area() {
+        // a thin line disappears with the nearest neighbour but not with the area filter
+        let f = frame(".#.. .#.. .#.. .#..");
+        assert_eq!(f.scale(2, 2, Filter::Nearest), frame(".. .."));
+        assert_eq!(f.scale(2, 2, Filter::Area), frame("#. #."));
+        // upscaling with the area filter is the same as the nearest neighbour
+        assert_eq!(f.scale(8, 8, Filter::Area), f.scale(8, 8, Filter::Nearest));
+        // a third of a pixel is not enough
+        assert_eq!(frame("#..").scale(1, 1, Filter::Area), frame("."));
+        assert_eq!(frame("##.").scale(1, 1, Filter::Area), frame("#"));
+    }
+
+    #[test]
+    fn test_scale2x() {
+        let f = frame("#.. .#. ..#");
+        assert_eq!(
+            f.scale2x(),
+            frame("##.... #.#... .###.. ..###. ...#.# ....##")
+        );
+        assert_eq!(frame("#").scale2x(), frame("## ##"));
+    }
+
+    #[test]
+    fn test_fit_to_panel() {
+        let f = frame("## ##");
+        assert_eq!(
+            f.fit_to_panel(6, 4, Fit::Fit, Filter::Nearest),
+            frame(".####. .####. .####. .####.")
+        );
+        assert_eq!(
+            f.fit_to_panel(6, 4, Fit::Letterbox, Filter::Nearest),
+            frame("...... ..##.. ..##.. ......")
+        );
+
+        let f = frame("#..# #..#");
+        assert_eq!(
+            f.fit_to_panel(2, 2, Fit::Crop, Filter::Nearest),
+            frame(".. ..")
+        );
+        assert_eq!(
+            f.fit_to_panel(2, 2, Fit::Fit, Filter::Nearest),
+            frame("#. ..")
+        );
+
+        // the height of a very wide frame would be rounded down to 0
+        let wide = Frame::empty(128, 8);
+        let fitted = wide.fit_to_panel(8, 64, Fit::Fit, Filter::Nearest);
+        assert_eq!(fitted.dimensions(), (8, 64));
+    }
+
+    #[test]
+    fn test_bongo_cat_on_other_panels() {
+        let path = format!("{}/bongo_cat_frames/idle1", env!("CARGO_MANIFEST_DIR"));
+        let cat = Frame::create_from_file(&path).unwrap();
+        for &(width, height) in &[(128, 32), (128, 64), (64, 48)] {
+            for &fit in &[Fit::Fit, Fit::Crop, Fit::Letterbox] {
+                let panel = cat.fit_to_panel(width, height, fit, Filter::Area);
+                assert_eq!(panel.dimensions(), (width, height));
+            }
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use crate::generator::{Codec, Encoded};
+use crate::{DiffError, Frame, UncompressError};
+
+/// a frame that the firmware wouldn't display correctly
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// the payload decodes to more bytes than the frame buffer, the firmware would write past
+    /// its end
+    Overflow {
+        name: String,
+        len: usize,
+        buffer_len: usize,
+    },
+    /// the payload decodes to fewer bytes than the frame buffer, the firmware would keep the end
+    /// of the previous frame
+    Short {
+        name: String,
+        len: usize,
+        buffer_len: usize,
+    },
+    /// the payload ends in the middle of a run, the firmware would read past the end of the array
+    Truncated {
+        name: String,
+        error: UncompressError,
+    },
+    /// the frame buffer can't hold a frame of the dimensions of the source frame
+    Invalid { name: String, error: String },
+    /// the base frame can't be used to undiff the frame buffer
+    Base { name: String, error: DiffError },
+    /// the decoded frame is not the source frame
+    Pixels {
+        name: String,
+        expected: Frame,
+        decoded: Frame,
+    },
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mismatch::Overflow {
+                name,
+                len,
+                buffer_len,
+            } => write!(
+                f,
+                "{}: decodes to {} bytes but the frame buffer is {} bytes",
+                name, len, buffer_len
+            ),
+            Mismatch::Short {
+                name,
+                len,
+                buffer_len,
+            } => write!(
+                f,
+                "{}: decodes to {} bytes but the frame buffer is {} bytes",
+                name, len, buffer_len
+            ),
+            Mismatch::Truncated { name, error } => write!(f, "{}: {}", name, error),
+            Mismatch::Invalid { name, error } => write!(f, "{}: {}", name, error),
+            Mismatch::Base { name, error } => write!(f, "{}: {}", name, error),
+            Mismatch::Pixels {
+                name,
+                expected,
+                decoded,
+            } => write!(
+                f,
+                "{}: {} pixels are different, `++` is an extra pixel and `--` a missing one\n{}",
+                name,
+                count_different_pixels(expected, decoded),
+                pixel_diff(expected, decoded)
+            ),
+        }
+    }
+}
+
+/// decode every frame like the firmware, with `uncompress2` and `undiff`, and compare it with
+/// its source frame. The frames of `encoded` must be in the same order as `frames`
+pub fn verify(frames: &[(String, Frame)], encoded: &Encoded, codec: Codec) -> Vec<Mismatch> {
+    frames
+        .iter()
+        .zip(&encoded.frames)
+        .filter_map(|((name, expected), (_, data))| {
+            let (width, height) = expected.dimensions();
+            let mut current = vec![0; encoded.buffer_len];
+
+            // `uncompress2` would panic where the firmware reads past the end of the array or
+            // writes past the end of the buffer
+            let len = match codec.decode(data) {
+                Ok(decoded) => decoded.len(),
+                Err(error) => {
+                    return Some(Mismatch::Truncated {
+                        name: name.clone(),
+                        error,
+                    })
+                }
+            };
+            if len > current.len() {
+                return Some(Mismatch::Overflow {
+                    name: name.clone(),
+                    len,
+                    buffer_len: encoded.buffer_len,
+                });
+            }
+            if len < current.len() {
+                return Some(Mismatch::Short {
+                    name: name.clone(),
+                    len,
+                    buffer_len: encoded.buffer_len,
+                });
+            }
+            match codec {
+                Codec::Rle => crate::uncompress2(data, &mut current),
+                Codec::Raw => current.copy_from_slice(data),
+            }
+            if let Some(base) = &encoded.base {
+                if let Err(error) = crate::try_undiff(base, &mut current) {
+                    return Some(Mismatch::Base {
+                        name: name.clone(),
+                        error,
+                    });
+                }
+            }
+
+            let decoded = match Frame::new(width, height, &current) {
+                Ok(decoded) => decoded,
+                Err(error) => {
+                    return Some(Mismatch::Invalid {
+                        name: name.clone(),
+                        error: error.to_string(),
+                    })
+                }
+            };
+            if &decoded != expected {
+                Some(Mismatch::Pixels {
+                    name: name.clone(),
+                    expected: expected.clone(),
+                    decoded,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// count the pixels that differ between two frames of the same dimensions
+pub fn count_different_pixels(expected: &Frame, other: &Frame) -> usize {
+    let (width, height) = expected.dimensions();
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|&(x, y)| expected.get(x, y) != other.get(x, y))
+        .count()
+}
+
+/// draw the differences between two frames of the same dimensions, the identical pixels are
+/// drawn normally, the pixels only on in `other` are drawn `++` and the ones missing `--`
+pub fn pixel_diff(expected: &Frame, other: &Frame) -> String {
+    let (width, height) = expected.dimensions();
+    let mut res = String::new();
+    for y in 0..height {
+        for x in 0..width {
+            res.push_str(match (expected.get(x, y), other.get(x, y)) {
+                (Some(true), Some(true)) => "██",
+                (Some(false), Some(true)) => "++",
+                (Some(true), _) => "--",
+                _ => "  ",
+            });
+        }
+        res.push('\n');
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{encode_with_codec, BaseStrategy};
+
+    fn bongo_cat() -> Vec<(String, Frame)> {
+        crate::bongo_cat(&["idle1", "prep", "tap1"])
+    }
+
+    #[test]
+    fn test_verify() {
+        let frames = bongo_cat();
+        for &codec in &[Codec::Rle, Codec::Raw] {
+            let encoded = encode_with_codec(&frames, &BaseStrategy::Best, codec).unwrap();
+            assert_eq!(verify(&frames, &encoded, codec), vec![]);
+        }
+    }
+
+    #[test]
+    fn test_trimmed_payload() {
+        // the old bug of the trimmed frames: the diff was truncated to the trimmed frame so the
+        // end of the buffer kept the end of the previous frame
+        let mut frames = bongo_cat();
+        frames[2].1.set(127, 39, true);
+        let mut encoded = encode_with_codec(
+            &frames,
+            &BaseStrategy::Frame("tap1".to_string()),
+            Codec::Raw,
+        )
+        .unwrap();
+        let base = encoded.base.clone().unwrap();
+        let trimmed = frames[1].1.output();
+        assert!(trimmed.len() < base.len());
+        encoded.frames[1].1 = crate::diff(&base[..trimmed.len()], &trimmed);
+
+        assert_eq!(
+            verify(&frames, &encoded, Codec::Raw),
+            vec![Mismatch::Short {
+                name: "prep".to_string(),
+                len: trimmed.len(),
+                buffer_len: 640
+            }]
+        );
+    }
+
+    #[test]
+    fn test_short_and_invalid() {
+        let mut frame = Frame::empty(8, 8);
+        frame.set(3, 3, true);
+        let frames = vec![("dot".to_string(), frame)];
+        let mut encoded = encode_with_codec(&frames, &BaseStrategy::None, Codec::Raw).unwrap();
+
+        // a payload cut to 2 of its 8 bytes
+        encoded.frames[0].1.truncate(2);
+        assert_eq!(
+            verify(&frames, &encoded, Codec::Raw),
+            vec![Mismatch::Short {
+                name: "dot".to_string(),
+                len: 2,
+                buffer_len: 8
+            }]
+        );
+
+        // the frame buffer is bigger than the frame
+        encoded.buffer_len = 16;
+        encoded.frames[0].1 = vec![0; 16];
+        match &verify(&frames, &encoded, Codec::Raw)[..] {
+            [Mismatch::Invalid { name, .. }] => assert_eq!(name, "dot"),
+            mismatches => panic!("unexpected mismatches {:?}", mismatches),
+        }
+    }
+
+    #[test]
+    fn test_truncated() {
+        let frames = vec![("dot".to_string(), Frame::empty(8, 8))];
+        let mut encoded = encode_with_codec(&frames, &BaseStrategy::None, Codec::Rle).unwrap();
+
+        // a run of 5 bytes without the byte to repeat
+        encoded.frames[0].1 = vec![5];
+        assert_eq!(
+            verify(&frames, &encoded, Codec::Rle),
+            vec![Mismatch::Truncated {
+                name: "dot".to_string(),
+                error: UncompressError::MissingRunValue { offset: 0 }
+            }]
+        );
+    }
+
+    #[test]
+    fn test_pixels() {
+        let frames = bongo_cat();
+        let mut encoded = encode_with_codec(&frames, &BaseStrategy::None, Codec::Raw).unwrap();
+        // the last byte holds the pixels (127, 32) to (127, 39)
+        *encoded.frames[1].1.last_mut().unwrap() ^= 0x80;
+
+        let mismatches = verify(&frames, &encoded, Codec::Raw);
+        assert_eq!(mismatches.len(), 1);
+        match &mismatches[0] {
+            Mismatch::Pixels {
+                name,
+                expected,
+                decoded,
+            } => {
+                assert_eq!(name, "prep");
+                assert_eq!(count_different_pixels(expected, decoded), 1);
+                assert_ne!(decoded.get(127, 39), expected.get(127, 39));
+            }
+            mismatch => panic!("unexpected mismatch {:?}", mismatch),
+        }
+    }
+
+    #[test]
+    fn test_overflow_and_base() {
+        let frames = bongo_cat();
+        let mut encoded =
+            encode_with_codec(&frames, &BaseStrategy::Suboptimal, Codec::Rle).unwrap();
+        encoded.frames[0].1.extend_from_slice(&[10, 0]);
+        encoded.base.as_mut().unwrap().push(0);
+
+        let mismatches = verify(&frames, &encoded, Codec::Rle);
+        assert_eq!(
+            mismatches[0],
+            Mismatch::Overflow {
+                name: "idle1".to_string(),
+                len: 650,
+                buffer_len: 640
+            }
+        );
+        assert_eq!(
+            mismatches[1],
+            Mismatch::Base {
+                name: "prep".to_string(),
+                error: DiffError::LengthMismatch {
+                    base: 641,
+                    other: 640
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn test_pixel_diff() {
+        let mut expected = Frame::empty(3, 8);
+        expected.set(0, 0, true);
+        expected.set(1, 0, true);
+        let mut other = Frame::empty(3, 8);
+        other.set(1, 0, true);
+        other.set(2, 0, true);
+
+        assert_eq!(count_different_pixels(&expected, &other), 2);
+        assert_eq!(
+            pixel_diff(&expected, &other).lines().next().unwrap(),
+            "--██++"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+/// the smoothing factor used by QMK
+const WPM_SMOOTHING: f64 = 0.0487;
+/// QMK considers a word is five characters
+const CHARS_PER_WORD: u64 = 5;
+/// after this many milliseconds without key press the WPM start decaying
+const DECAY_INTERVAL: u64 = 1000;
+
+/// compute the words per minute exactly like the original QMK `wpm.c`, including its integer
+/// truncations. The time is expressed in milliseconds.
+#[derive(Debug, Clone, Default)]
+pub struct Wpm {
+    current: u8,
+    timer: Option<u64>,
+}
+
+impl Wpm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// must be called on every key press, this is `update_wpm`
+    pub fn key_press(&mut self, now: u64) {
+        if let Some(timer) = self.timer {
+            // QMK would divide by zero here
+            let elapsed = now.saturating_sub(timer).max(1);
+            // the latest WPM is over 255 for the intervals under 47ms, it must not wrap
+            let latest = 60_000 / elapsed / CHARS_PER_WORD;
+            self.smooth(latest as f64);
+        }
+        self.timer = Some(now);
+    }
+
+    /// must be called regularly, this is `decay_wpm`
+    pub fn decay(&mut self, now: u64) {
+        if now.saturating_sub(self.timer.unwrap_or(0)) > DECAY_INTERVAL {
+            self.smooth(0.);
+            self.timer = Some(now);
+        }
+    }
+
+    /// the current WPM, this is `get_current_wpm`
+    pub fn get(&self) -> u8 {
+        self.current
+    }
+
+    /// only the smoothed WPM is clamped to a `u8`
+    fn smooth(&mut self, latest: f64) {
+        let current = self.current as f64;
+        self.current = (current + (latest - current) * WPM_SMOOTHING).clamp(0., 255.) as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wpm() {
+        let mut wpm = Wpm::new();
+        // the first key press only starts the timer
+        wpm.key_press(0);
+        assert_eq!(wpm.get(), 0);
+
+        // a key every 120ms is 100 WPM, the WPM slowly grows toward it but the truncations
+        // stop it once each step is smaller than one
+        for i in 1..=100 {
+            wpm.key_press(i * 120);
+        }
+        let fast = wpm.get();
+        assert_eq!(fast, 80);
+
+        // nothing happens before the decay interval
+        wpm.decay(100 * 120 + 1000);
+        assert_eq!(wpm.get(), fast);
+        wpm.decay(100 * 120 + 1001);
+        assert!(wpm.get() < fast);
+        for i in 2..200 {
+            wpm.decay(100 * 120 + 1001 * i);
+        }
+        assert_eq!(wpm.get(), 0);
+    }
+
+    #[test]
+    fn test_wpm_over_255() {
+        // a key every 40ms is 300 WPM, it saturates instead of wrapping
+        let mut wpm = Wpm::new();
+        for i in 0..=200 {
+            wpm.key_press(i * 40);
+        }
+        assert_eq!(wpm.get(), 255);
+
+        // and it keeps growing with a key every 10ms after a slower start
+        let mut wpm = Wpm::new();
+        for i in 0..=20 {
+            wpm.key_press(i * 120);
+        }
+        let slow = wpm.get();
+        for i in 1..=20 {
+            wpm.key_press(20 * 120 + i * 10);
+            assert!(wpm.get() > slow);
+        }
+    }
+}
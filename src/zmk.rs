@@ -0,0 +1,323 @@
+//! generate a ZMK module playing the animation of a manifest.
+//!
+//! ZMK draws with LVGL on top of the Zephyr display API instead of writing the OLED buffer, so
+//! the frames are either LVGL 1 bit images, or the compressed arrays with a decoder writing the
+//! frame buffer with `display_write` for a custom widget. The state machine is the one of
+//! `Animation::generate_c`, on the Zephyr uptime.
+
+use crate::animation::Animation;
+use crate::generator::{check_c_identifier, decoder_c, Encoded, Module};
+use crate::manifest::Manifest;
+use crate::{Frame, Rotation};
+
+/// how the frames are stored in the ZMK module
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZmkFormat {
+    /// uncompressed `LV_IMG_CF_INDEXED_1BIT` images displayed by an LVGL image
+    Lvgl,
+    /// the compressed (and diffed) frames, decoded in a frame buffer written with `display_write`
+    Raw,
+}
+
+impl std::str::FromStr for ZmkFormat {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lvgl" => Ok(ZmkFormat::Lvgl),
+            "raw" => Ok(ZmkFormat::Raw),
+            s => Err(format!("unknown ZMK format `{}`, expected `lvgl` or `raw`", s).into()),
+        }
+    }
+}
+
+/// the palette of the LVGL images: the pixels off are black and the pixels on white, as
+/// `lv_color32_t` (blue, green, red, alpha)
+const PALETTE: [u8; 8] = [0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff];
+
+/// the data of a `LV_IMG_CF_INDEXED_1BIT` LVGL image of a frame: the palette, then the rows of
+/// pixels, the leftmost pixel in the most significant bit and every row padded to a byte
+pub fn lvgl_indexed_1bit(frame: &Frame) -> Vec<u8> {
+    let (width, height) = frame.dimensions();
+    let stride = width.div_ceil(8);
+    let mut data = PALETTE.to_vec();
+    data.resize(PALETTE.len() + stride * height, 0);
+    for y in 0..height {
+        for x in 0..width {
+            if frame.get(x, y) == Some(true) {
+                data[PALETTE.len() + y * stride + x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+    }
+    data
+}
+
+/// generate the `<name>.h` and `<name>.c` ZMK module of a manifest, `name` is also the prefix of
+/// the functions of the module
+pub fn generate_module(
+    manifest: &Manifest,
+    name: &str,
+    format: ZmkFormat,
+) -> Result<Module, Box<dyn std::error::Error>> {
+    check_c_identifier(name)?;
+    let animation = Animation::from_manifest(manifest)?;
+    let upper = name.to_uppercase();
+    let current = &manifest.output.current;
+
+    // LVGL rotates the display itself, the raw frame buffer is rotated like for QMK
+    let (frames, encoded) = match format {
+        ZmkFormat::Lvgl => {
+            let frames = manifest.load_panel_frames()?;
+            let images: Vec<(String, Vec<u8>)> = frames
+                .iter()
+                .map(|(name, frame)| (name.clone(), lvgl_indexed_1bit(frame)))
+                .collect();
+            let encoded = Encoded {
+                buffer_len: images.first().map_or(0, |(_, data)| data.len()),
+                base: None,
+                frames: images,
+            };
+            (frames, encoded)
+        }
+        ZmkFormat::Raw => (manifest.load_buffer_frames()?, manifest.encode()?),
+    };
+    let (width, height) = frames
+        .first()
+        .map_or((manifest.width, manifest.height), |(_, frame)| {
+            frame.dimensions()
+        });
+    let rotation = match manifest.rotation {
+        Rotation::Deg0 => 0,
+        Rotation::Deg90 => 90,
+        Rotation::Deg180 => 180,
+        Rotation::Deg270 => 270,
+    };
+
+    let (usage, includes, prototype) = match format {
+        ZmkFormat::Lvgl => (
+            format!(
+                " * - call `{name}_create(parent)` where the widget is created, it returns the LVGL
+ *   image playing the animation. Set the display rotation to {rotation} degrees",
+                name = name,
+                rotation = rotation
+            ),
+            "#include <lvgl.h>\n",
+            format!("lv_obj_t *{}_create(lv_obj_t *parent);", name),
+        ),
+        ZmkFormat::Raw => (
+            format!(
+                " * - call `{name}_render(display)` periodically, the frame buffer is written with
+ *   `display_write` when the frame changes, it's already rotated by {rotation} degrees",
+                name = name,
+                rotation = rotation
+            ),
+            "#include <zephyr/device.h>\n#include <zephyr/drivers/display.h>\n",
+            format!("void {}_render(const struct device *display);", name),
+        ),
+    };
+
+    let header = format!(
+        "/* generated by kyria-oled, do not edit.
+ *
+ * A {width} x {height} animation for ZMK, to use it:
+ * - add `{name}.c` to the sources of your shield, and `CONFIG_ZMK_WPM=y` if the animation
+ *   changes with the WPM, without it the WPM is always 0
+{usage}
+ * - the key presses are counted by a listener of `zmk_keycode_state_changed`, call
+ *   `{name}_key_pressed()` yourself where these events are not raised
+ */
+#pragma once
+
+#include <stdbool.h>
+#include <stdint.h>
+{includes}
+#define {upper}_ROTATION {rotation}
+#define {upper}_WIDTH {width}
+#define {upper}_HEIGHT {height}
+
+void {name}_key_pressed(void);
+{prototype}
+",
+        width = width,
+        height = height,
+        name = name,
+        usage = usage,
+        includes = includes,
+        upper = upper,
+        rotation = rotation,
+        prototype = prototype,
+    );
+
+    let mut source = format!(
+        "/* generated by kyria-oled, do not edit, see {name}.h */
+#include <zephyr/kernel.h>
+#include <zmk/event_manager.h>
+#include <zmk/events/keycode_state_changed.h>
+#ifdef CONFIG_ZMK_WPM
+#include <zmk/wpm.h>
+#endif
+#include \"{name}.h\"
+
+static inline uint32_t timer_read32(void) {{
+    return k_uptime_get_32();
+}}
+
+static inline uint32_t timer_elapsed32(uint32_t last) {{
+    return k_uptime_get_32() - last;
+}}
+
+",
+        name = name,
+    );
+    if format == ZmkFormat::Raw {
+        source.push_str(&format!(
+            "static uint8_t {}[{}] = {{ 0 }};\n",
+            current, encoded.buffer_len
+        ));
+    }
+    source.push_str(&manifest.arrays_c_with(&encoded, ""));
+    source.push_str(&animation.generate_c());
+    source.push_str(&format!(
+        "
+void {name}_key_pressed(void) {{
+    animation_key_pressed();
+}}
+
+static int {name}_listener(const zmk_event_t *eh) {{
+    const struct zmk_keycode_state_changed *ev = as_zmk_keycode_state_changed(eh);
+    if (ev != NULL && ev->state) {{
+        animation_key_pressed();
+    }}
+    return ZMK_EV_EVENT_BUBBLE;
+}}
+
+ZMK_LISTENER({name}, {name}_listener);
+ZMK_SUBSCRIPTION({name}, zmk_keycode_state_changed);
+
+static uint8_t {name}_wpm(void) {{
+#ifdef CONFIG_ZMK_WPM
+    return zmk_wpm_get_state();
+#else
+    return 0;
+#endif
+}}
+",
+        name = name
+    ));
+
+    match format {
+        ZmkFormat::Lvgl => source.push_str(&format!(
+            "
+static lv_img_dsc_t {name}_image = {{
+    .header.cf = LV_IMG_CF_INDEXED_1BIT,
+    .header.always_zero = 0,
+    .header.reserved = 0,
+    .header.w = {width},
+    .header.h = {height},
+}};
+
+static void {name}_update(lv_timer_t *timer) {{
+    lv_obj_t *image = timer->user_data;
+    if (animation_update({name}_wpm()) || {name}_image.data == NULL) {{
+        {name}_image.data = animation_frame();
+        {name}_image.data_size = animation_frame_size();
+        lv_img_cache_invalidate_src(&{name}_image);
+        lv_img_set_src(image, &{name}_image);
+    }}
+}}
+
+lv_obj_t *{name}_create(lv_obj_t *parent) {{
+    lv_obj_t *image = lv_img_create(parent);
+    {name}_update(lv_timer_create({name}_update, 10, image));
+    return image;
+}}
+",
+            name = name,
+            width = width,
+            height = height
+        )),
+        ZmkFormat::Raw => {
+            source.push_str(&decoder_c(
+                &format!("{}_decode", name),
+                current,
+                encoded.base.as_ref().map(|_| manifest.output.base.as_str()),
+                false,
+            ));
+            source.push_str(&format!(
+                "
+void {name}_render(const struct device *display) {{
+    static bool drawn = false;
+    if (animation_update({name}_wpm()) || !drawn) {{
+        struct display_buffer_descriptor desc = {{
+            .buf_size = sizeof({current}),
+            .width = {width},
+            .height = {height},
+            .pitch = {width},
+        }};
+        drawn = true;
+        {name}_decode(animation_frame(), animation_frame_size());
+        display_write(display, 0, 0, &desc, {current});
+    }}
+}}
+",
+                name = name,
+                current = current,
+                width = width,
+                height = height
+            ));
+        }
+    }
+
+    Ok(Module {
+        name: name.to_string(),
+        header,
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lvgl_indexed_1bit() {
+        let mut frame = Frame::empty(10, 8);
+        frame.set(0, 0, true);
+        frame.set(9, 0, true);
+        frame.set(3, 7, true);
+        let data = lvgl_indexed_1bit(&frame);
+
+        assert_eq!(&data[..8], &PALETTE);
+        assert_eq!(data.len(), 8 + 2 * 8);
+        assert_eq!(&data[8..10], &[0x80, 0x40]);
+        assert_eq!(&data[8 + 7 * 2..], &[0x10, 0x00]);
+    }
+
+    #[test]
+    fn test_generate_module() {
+        let path = format!("{}/bongo_cat.manifest", env!("CARGO_MANIFEST_DIR"));
+        let manifest = Manifest::load(&path).unwrap();
+
+        let lvgl = generate_module(&manifest, "bongo_cat", ZmkFormat::Lvgl).unwrap();
+        assert!(lvgl
+            .header
+            .contains("lv_obj_t *bongo_cat_create(lv_obj_t *parent);"));
+        assert!(lvgl
+            .source
+            .contains("static const uint8_t BASE_TO_IDLE1[648]"));
+        assert!(lvgl.source.contains(".header.cf = LV_IMG_CF_INDEXED_1BIT"));
+        assert!(!lvgl.source.contains("PROGMEM"));
+        assert!(!lvgl.source.contains("CURRENT_FRAME"));
+
+        let raw = generate_module(&manifest, "bongo_cat", ZmkFormat::Raw).unwrap();
+        assert!(raw
+            .header
+            .contains("void bongo_cat_render(const struct device *display);"));
+        assert!(raw.source.contains("static uint8_t CURRENT_FRAME[640]"));
+        assert!(raw.source.contains("*(BASE_FRAME + i)"));
+        assert!(!raw.source.contains("PROGMEM"));
+        assert!(!raw.source.contains("pgm_read_byte"));
+
+        assert!("oled".parse::<ZmkFormat>().is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! the helpers shared by the integration tests.
+
+// every test uses only some of the helpers
+#![allow(dead_code)]
+
+use kyria_oled::animation::Animation;
+use kyria_oled::manifest::Manifest;
+use kyria_oled::{Frame, OutputMode};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+/// the path of a file of `tests/golden`
+pub fn golden_path(name: &str) -> String {
+    format!("{}/tests/golden/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+/// something stored in a golden file
+pub trait Golden: PartialEq {
+    fn load(path: &str) -> Self;
+    fn save(&self, path: &str);
+    /// how it's shown when it doesn't match its golden file
+    fn show(&self) -> String;
+}
+
+/// a frame, in the same format as the frames of the animations
+impl Golden for Frame {
+    fn load(path: &str) -> Self {
+        Frame::create_from_file(path).unwrap()
+    }
+
+    fn save(&self, path: &str) {
+        self.save_to_file(path).unwrap();
+    }
+
+    fn show(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// a text, like escape sequences, shown escaped
+impl Golden for String {
+    fn load(path: &str) -> Self {
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    fn save(&self, path: &str) {
+        std::fs::write(path, self).unwrap();
+    }
+
+    fn show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+/// compare `actual` with the golden file `name`, the golden file is written first when
+/// `UPDATE_GOLDEN` is set
+pub fn check_golden<T: Golden>(name: &str, actual: &T) {
+    let path = golden_path(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        actual.save(&path);
+    }
+    let golden = T::load(&path);
+    assert!(
+        &golden == actual,
+        "{} doesn't match its golden file, expected:\n{}\ngot:\n{}",
+        name,
+        golden.show(),
+        actual.show()
+    );
+}
+
+/// the system C compiler, `$CC` or else `cc`, if there's one
+pub fn c_compiler() -> Option<String> {
+    let cc = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    match Command::new(&cc).arg("--version").output() {
+        Ok(_) => Some(cc),
+        Err(_) => {
+            eprintln!("`{}` not found, the C code is not tested", cc);
+            None
+        }
+    }
+}
+
+/// the typing session replayed by the module tests: the time of the last frame, the time
+/// between two frames and the time between two key presses, in ms
+pub const END: u64 = 8000;
+pub const STEP: u64 = 10;
+pub const KEY_INTERVAL: u64 = 150;
+
+/// the WPM from every time in ms until the next one, it goes up to make the cat tap, then down
+/// to make it idle again
+pub const WPM_SCHEDULE: &[(u64, u8)] = &[(0, 0), (2000, 20), (4000, 60), (6000, 5)];
+
+/// the WPM of the schedule at `now`
+pub fn wpm(now: u64) -> u8 {
+    WPM_SCHEDULE
+        .iter()
+        .rev()
+        .find(|(start, _)| now >= *start)
+        .unwrap()
+        .1
+}
+
+/// the C macro `WPM(now)` returning the same WPM as `wpm`
+fn wpm_define() -> String {
+    let mut define = "-DWPM(now)=(".to_string();
+    for step in WPM_SCHEDULE.windows(2) {
+        define.push_str(&format!("(now) < {} ? {} : ", step[1].0, step[0].1));
+    }
+    define.push_str(&format!("{})", WPM_SCHEDULE.last().unwrap().1));
+    define
+}
+
+/// compile the `sources` of `dir` with the macros of the typing session (`END`, `STEP`,
+/// `KEY_INTERVAL` and `WPM(now)`) and the `defines`, then run the binary and return what it
+/// printed
+pub fn compile_and_run(cc: &str, dir: &Path, defines: &[&str], sources: &[&str]) -> String {
+    let binary = dir.join("module");
+    let status = Command::new(cc)
+        .arg("-O2")
+        .arg("-Wall")
+        .args(defines)
+        .arg(format!("-DEND={}", END))
+        .arg(format!("-DSTEP={}", STEP))
+        .arg(format!("-DKEY_INTERVAL={}", KEY_INTERVAL))
+        .arg(wpm_define())
+        .arg("-I")
+        .arg(dir)
+        .arg("-o")
+        .arg(&binary)
+        .args(sources.iter().map(|source| dir.join(source)))
+        .status()
+        .unwrap();
+    assert!(status.success(), "can't compile {:?}", sources);
+
+    let output = Command::new(&binary).output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// the frames the rust `Player` displays during the typing session, as lines of the time
+/// followed by the frame buffer in hexadecimal
+pub fn player_frames(manifest: &Manifest) -> String {
+    let frames: HashMap<String, String> = manifest
+        .load_buffer_frames()
+        .unwrap()
+        .into_iter()
+        .map(|(name, frame)| {
+            let hex = frame
+                .output_with(OutputMode::Full)
+                .data
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect();
+            (name, hex)
+        })
+        .collect();
+    let animation = Animation::from_manifest(manifest).unwrap();
+    let mut player = animation.player();
+    let mut expected = String::new();
+    for now in (0..=END).step_by(STEP as usize) {
+        if now % KEY_INTERVAL == 0 {
+            player.key_press(now);
+        }
+        if player.update(now, wpm(now)) || now == 0 {
+            let frame = manifest.resolve(player.frame()).unwrap();
+            expected.push_str(&format!("{} {}\n", now, frames[frame]));
+        }
+    }
+    expected
+}
+
+/// compare the frames written by the C with the ones of `player_frames`
+pub fn check_frames(written: &str, expected: &str) {
+    assert!(written.lines().count() > 10);
+    for (line, (c, rust)) in written.lines().zip(expected.lines()).enumerate() {
+        assert_eq!(
+            c.split(' ').next(),
+            rust.split(' ').next(),
+            "frame {} is not written at the same time",
+            line
+        );
+        assert!(c == rust, "frame {} is different", line);
+    }
+    assert_eq!(written.lines().count(), expected.lines().count());
+}
@@ -0,0 +1,77 @@
+//! compare the drawing primitives with golden frames stored in `tests/golden`, in the same
+//! format as the frames of the animations.
+//! Run the tests with `UPDATE_GOLDEN=1` to regenerate the golden frames after a change.
+
+mod common;
+
+use common::check_golden;
+use kyria_oled::Frame;
+
+#[test]
+fn lines() {
+    let mut frame = Frame::empty(24, 16);
+    frame.draw_line(0, 0, 23, 15, true);
+    frame.draw_line(0, 15, 23, 0, true);
+    frame.draw_line(2, 8, 21, 8, true);
+    frame.draw_line(12, 1, 12, 14, true);
+    // going outside of the frame
+    frame.draw_line(-5, 3, 5, -2, true);
+    check_golden("lines", &frame);
+}
+
+#[test]
+fn rectangles() {
+    let mut frame = Frame::empty(24, 16);
+    frame.draw_rect(1, 1, 10, 6, true);
+    frame.fill_rect(13, 1, 10, 6, true);
+    frame.fill_rect(15, 3, 6, 2, false);
+    frame.draw_rect(-2, 9, 8, 4, true);
+    frame.fill_rect(20, 12, 8, 8, true);
+    check_golden("rectangles", &frame);
+}
+
+#[test]
+fn circles() {
+    let mut frame = Frame::empty(32, 24);
+    frame.draw_circle(11, 11, 10, true);
+    frame.draw_circle(11, 11, 4, true);
+    frame.draw_circle(11, 11, 0, true);
+    frame.draw_circle(30, 20, 6, true);
+    check_golden("circles", &frame);
+}
+
+#[test]
+fn ellipses() {
+    let mut frame = Frame::empty(32, 24);
+    frame.draw_ellipse(15, 11, 14, 6, true);
+    frame.draw_ellipse(15, 11, 3, 10, true);
+    frame.draw_ellipse(28, 2, 5, 3, true);
+    // the flat ellipses are lines
+    frame.draw_ellipse(8, 22, 5, 0, true);
+    frame.draw_ellipse(31, 15, 0, 3, true);
+    check_golden("ellipses", &frame);
+}
+
+#[test]
+fn polygons() {
+    let mut frame = Frame::empty(32, 24);
+    frame.draw_polygon(&[(2, 20), (10, 2), (18, 20)], true);
+    frame.draw_polygon(&[(20, 4), (30, 4), (26, 12), (30, 22), (20, 22)], true);
+    // a single point polygon is a pixel
+    frame.draw_polygon(&[(0, 0)], true);
+    check_golden("polygons", &frame);
+}
+
+#[test]
+fn progress_bar() {
+    // a WPM bar over the bongo cat
+    let mut frame = Frame::create_from_file(&format!(
+        "{}/bongo_cat_frames/idle1",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .unwrap();
+    frame.fill_rect(80, 30, 46, 8, false);
+    frame.draw_rect(80, 30, 46, 8, true);
+    frame.fill_rect(82, 32, 30, 4, true);
+    check_golden("progress_bar", &frame);
+}
@@ -83,13 +83,13 @@ fn main() {
     let tap = tap.iter().cycle();
 
     for idle in idle.take(10) {
-        setup_current_frame(&mut CURRENT_FRAME, &idle);
+        setup_current_frame(&mut CURRENT_FRAME, idle);
         Frame::new(width, height, &CURRENT_FRAME).unwrap().print();
         std::thread::sleep(std::time::Duration::from_millis(200));
         println!("\x1B[41A");
     }
     for tap in tap.take(10) {
-        setup_current_frame(&mut CURRENT_FRAME, &tap);
+        setup_current_frame(&mut CURRENT_FRAME, tap);
         Frame::new(width, height, &CURRENT_FRAME).unwrap().print();
         std::thread::sleep(std::time::Duration::from_millis(200));
         println!("\x1B[41A");
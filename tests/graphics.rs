@@ -0,0 +1,23 @@
+//! compare the Sixel and Kitty images of a frame with the escape sequences stored in
+//! `tests/golden`.
+//! Run the tests with `UPDATE_GOLDEN=1` to regenerate the golden files after a change.
+
+mod common;
+
+use common::{check_golden, golden_path};
+use kyria_oled::graphics::{kitty, sixel, Palette};
+use kyria_oled::Frame;
+
+fn circles() -> Frame {
+    Frame::create_from_file(&golden_path("circles")).unwrap()
+}
+
+#[test]
+fn sixel_blue() {
+    check_golden("circles.sixel", &sixel(&circles(), 2, Palette::Blue));
+}
+
+#[test]
+fn kitty_white() {
+    check_golden("circles.kitty", &kitty(&circles(), 2, Palette::White));
+}
@@ -0,0 +1,89 @@
+//! compile the QMK module generated for the bongo cat with the system C compiler (`$CC`, or
+//! else `cc`) against a stub of the QMK functions it uses, and check that it writes the same
+//! frames at the same time as the rust `Player`. The test is skipped when there is no C
+//! compiler.
+
+mod common;
+
+use kyria_oled::manifest::Manifest;
+use kyria_oled::qmk::generate_module;
+use std::path::Path;
+
+const QMK_STUB: &str = r#"
+#pragma once
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdio.h>
+
+#define PROGMEM
+#define pgm_read_byte(address) (*(const uint8_t *)(address))
+#define OLED_ROTATION_0 0
+#define OLED_ROTATION_90 1
+#define OLED_ROTATION_180 2
+#define OLED_ROTATION_270 3
+
+extern uint32_t now;
+extern uint8_t wpm;
+
+static inline uint32_t timer_read32(void) { return now; }
+static inline uint32_t timer_elapsed32(uint32_t last) { return now - last; }
+static inline uint8_t get_current_wpm(void) { return wpm; }
+void oled_write_raw(const char *data, uint16_t size);
+"#;
+
+const MAIN: &str = r#"
+#include "qmk_stub.h"
+#include "bongo_cat.h"
+
+uint32_t now = 0;
+uint8_t wpm = 0;
+
+/* print the time and the frame in hexadecimal */
+void oled_write_raw(const char *data, uint16_t size) {
+    printf("%u ", (unsigned)now);
+    for (uint16_t i = 0; i < size; i++) {
+        printf("%02x", (uint8_t)data[i]);
+    }
+    printf("\n");
+}
+
+int main(void) {
+    for (now = 0; now <= END; now += STEP) {
+        wpm = WPM(now);
+        if (now % KEY_INTERVAL == 0) {
+            bongo_cat_key_pressed();
+        }
+        bongo_cat_render();
+    }
+    return 0;
+}
+"#;
+
+#[test]
+fn bongo_cat_module() {
+    let cc = match common::c_compiler() {
+        Some(cc) => cc,
+        None => return,
+    };
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let manifest = Manifest::load(root.join("bongo_cat.manifest").to_str().unwrap()).unwrap();
+    let module = generate_module(&manifest, "bongo_cat").unwrap();
+
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("qmk_module");
+    std::fs::create_dir_all(&dir).unwrap();
+    module.save(&dir).unwrap();
+    std::fs::write(dir.join("qmk_stub.h"), QMK_STUB).unwrap();
+    std::fs::write(dir.join("main.c"), MAIN).unwrap();
+
+    let written = common::compile_and_run(
+        &cc,
+        &dir,
+        &["-DWPM_ENABLE", "-DQMK_KEYBOARD_H=\"qmk_stub.h\""],
+        &["main.c", "bongo_cat.c"],
+    );
+
+    // what the rust player displays at the same time
+    let expected = common::player_frames(&manifest);
+    common::check_frames(&written, &expected);
+}
@@ -0,0 +1,277 @@
+//! compile `uncompress.c` with the system C compiler (`$CC`, or else `cc`) and check that it
+//! decodes thousands of `compress` outputs exactly like `uncompress` and `undiff`.
+//! The decoder is built once with a signed `char` and once with an unsigned `char`, like on AVR
+//! and ARM. The tests are skipped when there is no C compiler.
+
+mod common;
+
+use kyria_oled::{
+    compress, diff, find_suboptimal_base_frame, uncompress, undiff, Frame, OutputMode,
+};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// replace the demo `main` of `uncompress.c` by one decoding the cases read on stdin.
+/// Every case is: the length of the data, the length of the output and the length of the base
+/// (0 when there is no base) as little endian `uint32_t`, then the data and the base. The
+/// decoded output of every case is written on stdout
+const HARNESS: &str = r#"
+#define main uncompress_c_demo
+#include "UNCOMPRESS_C"
+#undef main
+
+#include <stdint.h>
+#include <stdlib.h>
+
+static uint32_t read_u32(void) {
+    unsigned char bytes[4];
+    if (fread(bytes, 1, 4, stdin) != 4) {
+        exit(0);
+    }
+    return bytes[0] | bytes[1] << 8 | (uint32_t)bytes[2] << 16 | (uint32_t)bytes[3] << 24;
+}
+
+int main(void) {
+    for (;;) {
+        uint32_t data_len = read_u32();
+        uint32_t output_len = read_u32();
+        uint32_t base_len = read_u32();
+        char *data = malloc(data_len + 1);
+        char *output = calloc(output_len + 1, 1);
+        char *base = malloc(base_len + 1);
+        if (fread(data, 1, data_len, stdin) != data_len
+            || fread(base, 1, base_len, stdin) != base_len) {
+            return 1;
+        }
+
+        uncompress(data, data_len, output);
+        if (base_len) {
+            undiff(base, output, base_len);
+        }
+        fwrite(output, 1, output_len, stdout);
+
+        free(data);
+        free(output);
+        free(base);
+    }
+}
+"#;
+
+struct Case {
+    description: String,
+    data: Vec<u8>,
+    base: Option<Vec<u8>>,
+}
+
+impl Case {
+    fn new(description: impl Into<String>, raw: &[u8], base: Option<&[u8]>) -> Self {
+        let data = match base {
+            Some(base) => compress(&diff(base, raw)),
+            None => compress(raw),
+        };
+        Case {
+            description: description.into(),
+            data,
+            base: base.map(<[u8]>::to_vec),
+        }
+    }
+
+    /// what the rust decoder gives
+    fn expected(&self) -> Vec<u8> {
+        let mut output = uncompress(&self.data);
+        if let Some(base) = &self.base {
+            undiff(base, &mut output);
+        }
+        output
+    }
+}
+
+/// a xorshift, we don't need a good generator, only a reproducible one
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+fn random_cases() -> Vec<Case> {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    (0..3000)
+        .map(|i| {
+            let len = rng.below(1200) + 1;
+            let mut raw = Vec::with_capacity(len);
+            while raw.len() < len {
+                // a mix of long runs, to get repeat control bytes, and of noise, to get literal
+                // ones, most values have their high bit set to catch the signed chars
+                let byte = match i % 3 {
+                    0 => rng.next() as u8,
+                    1 => 0x80 | rng.next() as u8,
+                    _ => [0, 0xff, 0x80, 0x7f][rng.below(4)],
+                };
+                let run = if rng.below(2) == 0 { 1 } else { rng.below(300) };
+                raw.resize(raw.len() + run.min(len - raw.len()), byte);
+            }
+            let base: Vec<u8> = (0..len).map(|_| rng.next() as u8).collect();
+            Case::new(
+                format!("random case {}", i),
+                &raw,
+                Some(&base).filter(|_| i % 2 == 0).map(|base| &base[..]),
+            )
+        })
+        .collect()
+}
+
+fn bongo_cat_cases() -> Vec<Case> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("bongo_cat_frames");
+    let mut names: Vec<PathBuf> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    names.sort();
+    let frames: Vec<(String, Vec<u8>)> = names
+        .iter()
+        .map(|path| {
+            let frame = Frame::create_from_file(path.to_str().unwrap()).unwrap();
+            (
+                path.file_name().unwrap().to_str().unwrap().to_string(),
+                frame.output_with(OutputMode::Full).data,
+            )
+        })
+        .collect();
+    let outputs: Vec<Vec<u8>> = frames.iter().map(|(_, output)| output.clone()).collect();
+    let suboptimal = find_suboptimal_base_frame(&outputs);
+
+    let mut cases = Vec::new();
+    for (name, output) in &frames {
+        cases.push(Case::new(name.clone(), output, None));
+        cases.push(Case::new(
+            format!("{} on the suboptimal base", name),
+            output,
+            Some(&suboptimal),
+        ));
+        for (base_name, base) in &frames {
+            cases.push(Case::new(
+                format!("{} on {}", name, base_name),
+                output,
+                Some(base),
+            ));
+        }
+    }
+    cases
+}
+
+/// compile the harness, return `None` if there is no C compiler
+fn compile(char_flag: &str) -> Option<PathBuf> {
+    let cc = common::c_compiler()?;
+
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    // the tests run in parallel so every build has its own source
+    let source = dir.join(format!("uncompress_harness{}.c", char_flag));
+    let uncompress_c = Path::new(env!("CARGO_MANIFEST_DIR")).join("uncompress.c");
+    std::fs::write(
+        &source,
+        HARNESS.replace("UNCOMPRESS_C", uncompress_c.to_str().unwrap()),
+    )
+    .unwrap();
+
+    let binary = dir.join(format!("uncompress{}", char_flag));
+    let status = Command::new(&cc)
+        .arg(char_flag)
+        .arg("-O2")
+        .arg("-o")
+        .arg(&binary)
+        .arg(&source)
+        .status()
+        .unwrap();
+    assert!(status.success(), "can't compile the C decoder");
+    Some(binary)
+}
+
+/// decode all the cases with the C decoder and return the description of the failing ones
+fn run(binary: &Path, cases: &[Case]) -> Vec<String> {
+    let mut input = Vec::new();
+    let expected: Vec<Vec<u8>> = cases.iter().map(Case::expected).collect();
+    for (case, expected) in cases.iter().zip(&expected) {
+        let base = case.base.as_deref().unwrap_or(&[]);
+        for len in &[case.data.len(), expected.len(), base.len()] {
+            input.extend_from_slice(&(*len as u32).to_le_bytes());
+        }
+        input.extend_from_slice(&case.data);
+        input.extend_from_slice(base);
+    }
+
+    let mut child = Command::new(binary)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let writer = std::thread::spawn(move || stdin.write_all(&input).unwrap());
+    let output = child.wait_with_output().unwrap();
+    writer.join().unwrap();
+    if !output.status.success() {
+        return vec!["the C decoder crashed, it probably wrote past the end of its output".into()];
+    }
+
+    let mut decoded = &output.stdout[..];
+    let mut failures = Vec::new();
+    for (case, expected) in cases.iter().zip(&expected) {
+        let len = expected.len().min(decoded.len());
+        let (c, rest) = decoded.split_at(len);
+        decoded = rest;
+        if c != &expected[..] {
+            let position = c
+                .iter()
+                .zip(expected)
+                .position(|(c, rust)| c != rust)
+                .unwrap_or(len);
+            failures.push(format!(
+                "{}: first difference at byte {} ({:?} in C, {:?} in rust)",
+                case.description,
+                position,
+                c.get(position),
+                expected.get(position)
+            ));
+        }
+    }
+    failures
+}
+
+fn check(char_flag: &str, description: &str) {
+    let binary = match compile(char_flag) {
+        Some(binary) => binary,
+        None => return,
+    };
+    let cases: Vec<Case> = random_cases()
+        .into_iter()
+        .chain(bongo_cat_cases())
+        .collect();
+    let failures = run(&binary, &cases);
+    assert!(
+        failures.is_empty(),
+        "the C decoder built with {} doesn't match the rust one on {} of the {} cases:\n{}",
+        description,
+        failures.len(),
+        cases.len(),
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn signed_char() {
+    check("-fsigned-char", "a signed `char`");
+}
+
+#[test]
+fn unsigned_char() {
+    check("-funsigned-char", "an unsigned `char`");
+}
@@ -0,0 +1,168 @@
+//! compile the raw ZMK module generated for the bongo cat with the system C compiler (`$CC`, or
+//! else `cc`) against a stub of the Zephyr and ZMK headers it includes, and check that it writes
+//! the same frames at the same time as the rust `Player`. The key presses go through the ZMK
+//! listener. The test is skipped when there is no C compiler.
+
+mod common;
+
+use kyria_oled::manifest::Manifest;
+use kyria_oled::zmk::{generate_module, ZmkFormat};
+use std::path::Path;
+
+/// the stub headers, by path
+const STUBS: &[(&str, &str)] = &[
+    (
+        "zephyr/kernel.h",
+        r#"
+#pragma once
+#include <stdint.h>
+
+extern uint32_t now;
+
+static inline uint32_t k_uptime_get_32(void) { return now; }
+"#,
+    ),
+    (
+        "zephyr/device.h",
+        r#"
+#pragma once
+
+struct device {
+    int unused;
+};
+"#,
+    ),
+    (
+        "zephyr/drivers/display.h",
+        r#"
+#pragma once
+#include <stdint.h>
+#include <zephyr/device.h>
+
+struct display_buffer_descriptor {
+    uint32_t buf_size;
+    uint16_t width;
+    uint16_t height;
+    uint16_t pitch;
+};
+
+int display_write(const struct device *dev, uint16_t x, uint16_t y,
+                  const struct display_buffer_descriptor *desc, const void *buf);
+"#,
+    ),
+    (
+        "zmk/event_manager.h",
+        r#"
+#pragma once
+#include <stdbool.h>
+#include <stddef.h>
+
+#define ZMK_EV_EVENT_BUBBLE 0
+
+/* the only event is the key state change */
+typedef struct zmk_keycode_state_changed zmk_event_t;
+
+/* the listener is reachable from main.c */
+#define ZMK_LISTENER(mod, cb) int (*const zmk_listener_##mod)(const zmk_event_t *) = cb
+#define ZMK_SUBSCRIPTION(mod, ev) extern int (*const zmk_listener_##mod)(const zmk_event_t *)
+"#,
+    ),
+    (
+        "zmk/events/keycode_state_changed.h",
+        r#"
+#pragma once
+#include <stdbool.h>
+#include <zmk/event_manager.h>
+
+struct zmk_keycode_state_changed {
+    bool state;
+};
+
+static inline const struct zmk_keycode_state_changed *
+as_zmk_keycode_state_changed(const zmk_event_t *eh) {
+    return eh;
+}
+"#,
+    ),
+    (
+        "zmk/wpm.h",
+        r#"
+#pragma once
+#include <stdint.h>
+
+extern uint8_t wpm;
+
+static inline uint8_t zmk_wpm_get_state(void) { return wpm; }
+"#,
+    ),
+];
+
+const MAIN: &str = r#"
+#include <stdio.h>
+#include <zephyr/kernel.h>
+#include <zmk/event_manager.h>
+#include <zmk/events/keycode_state_changed.h>
+#include "bongo_cat.h"
+
+uint32_t now = 0;
+uint8_t wpm = 0;
+
+extern int (*const zmk_listener_bongo_cat)(const zmk_event_t *);
+
+/* print the time and the frame in hexadecimal */
+int display_write(const struct device *dev, uint16_t x, uint16_t y,
+                  const struct display_buffer_descriptor *desc, const void *buf) {
+    const uint8_t *data = buf;
+    printf("%u ", (unsigned)now);
+    for (uint32_t i = 0; i < desc->buf_size; i++) {
+        printf("%02x", data[i]);
+    }
+    printf("\n");
+    return 0;
+}
+
+int main(void) {
+    struct device display = { 0 };
+    struct zmk_keycode_state_changed pressed = { .state = true };
+    struct zmk_keycode_state_changed released = { .state = false };
+    for (now = 0; now <= END; now += STEP) {
+        wpm = WPM(now);
+        if (now % KEY_INTERVAL == 0) {
+            zmk_listener_bongo_cat(&pressed);
+        } else if (now % KEY_INTERVAL == STEP) {
+            /* the releases are not key presses */
+            zmk_listener_bongo_cat(&released);
+        }
+        bongo_cat_render(&display);
+    }
+    return 0;
+}
+"#;
+
+#[test]
+fn bongo_cat_raw_module() {
+    let cc = match common::c_compiler() {
+        Some(cc) => cc,
+        None => return,
+    };
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let manifest = Manifest::load(root.join("bongo_cat.manifest").to_str().unwrap()).unwrap();
+    let module = generate_module(&manifest, "bongo_cat", ZmkFormat::Raw).unwrap();
+
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("zmk_module");
+    for (path, stub) in STUBS {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, stub).unwrap();
+    }
+    module.save(&dir).unwrap();
+    std::fs::write(dir.join("main.c"), MAIN).unwrap();
+
+    let written =
+        common::compile_and_run(&cc, &dir, &["-DCONFIG_ZMK_WPM"], &["main.c", "bongo_cat.c"]);
+
+    // what the rust player displays at the same time
+    let expected = common::player_frames(&manifest);
+    common::check_frames(&written, &expected);
+}